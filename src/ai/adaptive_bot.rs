@@ -0,0 +1,134 @@
+use ai::fleet_tracker::FleetTracker;
+use ai::PlacementHistory;
+use ai::Shooter;
+use ai::View;
+use analysis::probability_map;
+use common::CellStatus;
+use common::Dimensional;
+use common::ShipTypeContainer;
+use random::Rng;
+use results::ShootOk;
+use std::collections::HashSet;
+use ::Dimension;
+
+/// How strongly the opponent's habits bias the bot: a cell that held a ship in every past game
+/// counts up to this many times more than a cell that never did.
+const BIAS: f64 = 3.0;
+
+/// A bot learning from an opponent's habits. It plays like `ProbabilityBot`, but favours the
+/// cells where the opponent put their ships in past games, as recorded in a
+/// `PlacementHistory`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct AdaptiveBot {
+    rng: Rng,
+    history: PlacementHistory,
+    fleet: FleetTracker,
+    fired: HashSet<(Dimension, Dimension)>,
+}
+
+impl AdaptiveBot {
+    /// Creates a new instance hunting the ship types of `fleet`, e.g. a `PreGame` or a `Game`,
+    /// and biased by `history`. A history of a different battlefield size is ignored. Decoys
+    /// and submerged ships are ignored. Bots with the same history and seed pick the same
+    /// cells.
+    pub fn new<T: ShipTypeContainer>(
+        fleet: &T,
+        history: &PlacementHistory,
+        seed: u64,
+    ) -> Self {
+        AdaptiveBot {
+            rng: Rng::new(seed),
+            history: history.clone(),
+            fleet: FleetTracker::new(fleet),
+            fired: HashSet::new(),
+        }
+    }
+
+    /// Returns the lengths of the ships the bot believes to be still afloat.
+    pub fn remaining_ships(&self) -> &[Dimension] {
+        self.fleet.remaining()
+    }
+}
+
+impl Shooter for AdaptiveBot {
+    fn next_shot(
+        &mut self,
+        view: &View,
+    ) -> Option<(Dimension, Dimension)> {
+        let mut view = view.clone();
+        for &(x, y) in self.fleet.sunk() {
+            view.set(x, y, CellStatus::Miss);
+        }
+        let known_size = (self.history.width(), self.history.height()) == (view.width(), view.height());
+
+        let map = probability_map(&view, self.fleet.remaining());
+        let candidates = map.best_weighted_cells(
+            |x, y| if known_size { 1.0 + (BIAS - 1.0) * self.history.frequency(x, y) } else { 1.0 },
+            |x, y| view.is_unknown(x, y) && !self.fired.contains(&(x, y)),
+        );
+        if candidates.is_empty() {
+            return None;
+        }
+        let target = candidates[self.rng.below(candidates.len())];
+        self.fired.insert(target);
+        Some(target)
+    }
+
+    fn record_result(
+        &mut self,
+        x: Dimension,
+        y: Dimension,
+        result: ShootOk,
+    ) {
+        if result == ShootOk::Armored {
+            self.fired.remove(&(x, y));
+        }
+        self.fleet.record_result(x, y, result);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ai::PlacementHistory;
+    use ai::test_util::play_against_idle;
+    use common::Orientation::*;
+    use common::Player::*;
+    use game::Game;
+    use pregame::PreGame;
+    use super::AdaptiveBot;
+
+    fn build_test_game() -> Game {
+        let mut pregame = PreGame::new(8, 8).unwrap();
+        let cruiser = pregame.add_ship_type("Cruiser", 3).unwrap();
+        let corvette = pregame.add_ship_type("Corvette", 2).unwrap();
+        for &player in &[P1, P2] {
+            pregame.place_ship(player, &cruiser, 0, 7, Horizontal).unwrap();
+            pregame.place_ship(player, &corvette, 7, 0, Vertical).unwrap();
+        }
+        pregame.start().unwrap()
+    }
+
+    fn shots_to_win(history: &PlacementHistory) -> (usize, Game) {
+        let mut game = build_test_game();
+        let mut bot = AdaptiveBot::new(&game, history, 1);
+        let shots = play_against_idle(&mut game, &mut bot, 3, 3).len();
+        (shots, game)
+    }
+
+    #[test]
+    fn should_learn_where_the_opponent_places_ships() {
+        let mut history = PlacementHistory::new(8, 8);
+        let (naive, game) = shots_to_win(&history);
+        history.record_game(&game, P2).unwrap();
+
+        let (informed, _) = shots_to_win(&history);
+        assert!(informed < naive);
+        assert!(informed <= 10);
+    }
+
+    #[test]
+    fn should_ignore_histories_of_other_sizes() {
+        let (shots, _) = shots_to_win(&PlacementHistory::new(8, 8));
+        assert_eq!(shots, shots_to_win(&PlacementHistory::new(5, 5)).0);
+    }
+}
@@ -0,0 +1,88 @@
+/// How well a `TunableBot` plays. The default is flawless play, equal to `ProbabilityBot`.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Difficulty {
+    random_shots: f64,
+    forgotten_hits: f64,
+    lookahead: Option<usize>,
+}
+
+impl Default for Difficulty {
+    fn default() -> Self {
+        Difficulty {
+            random_shots: 0.0,
+            forgotten_hits: 0.0,
+            lookahead: None,
+        }
+    }
+}
+
+impl Difficulty {
+    /// A bot for beginners: often shoots at random, rarely follows up hits and only plans for
+    /// one ship.
+    pub fn easy() -> Self {
+        Difficulty::default()
+            .with_random_shots(0.5)
+            .with_forgotten_hits(0.6)
+            .with_lookahead(1)
+    }
+
+    /// A bot for casual players.
+    pub fn medium() -> Self {
+        Difficulty::default()
+            .with_random_shots(0.2)
+            .with_forgotten_hits(0.25)
+            .with_lookahead(2)
+    }
+
+    /// A bot making an occasional mistake.
+    pub fn hard() -> Self {
+        Difficulty::default()
+            .with_random_shots(0.05)
+            .with_forgotten_hits(0.05)
+    }
+
+    /// Sets the chance of a shot at a random cell instead of the best one, between 0 and 1.
+    pub fn with_random_shots(
+        mut self,
+        chance: f64,
+    ) -> Self {
+        self.random_shots = chance;
+        self
+    }
+
+    /// Sets the chance of ignoring the hits of ships not yet destroyed when picking a cell,
+    /// between 0 and 1.
+    pub fn with_forgotten_hits(
+        mut self,
+        chance: f64,
+    ) -> Self {
+        self.forgotten_hits = chance;
+        self
+    }
+
+    /// Sets the number of remaining ships the bot plans for, longest first. The others are
+    /// ignored until these are destroyed.
+    pub fn with_lookahead(
+        mut self,
+        ships: usize,
+    ) -> Self {
+        self.lookahead = Some(ships);
+        self
+    }
+
+    /// Returns the chance of a shot at a random cell, see `with_random_shots`.
+    pub fn random_shots(&self) -> f64 {
+        self.random_shots
+    }
+
+    /// Returns the chance of ignoring unresolved hits, see `with_forgotten_hits`.
+    pub fn forgotten_hits(&self) -> f64 {
+        self.forgotten_hits
+    }
+
+    /// Returns the number of ships the bot plans for, `None` if it plans for all of them, see
+    /// `with_lookahead`.
+    pub fn lookahead(&self) -> Option<usize> {
+        self.lookahead
+    }
+}
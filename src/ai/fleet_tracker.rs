@@ -0,0 +1,163 @@
+use common::Layer;
+use common::ShipTypeContainer;
+use results::ShootOk;
+use std::collections::HashSet;
+use ::Dimension;
+
+/// The directions ships can extend in on a square grid.
+pub const DIRECTIONS: [(isize, isize); 2] = [(1, 0), (0, 1)];
+
+/// Keeps track of the opponent's fleet from a bot's shot results: the lengths of the ships
+/// still afloat, the hits that don't belong to a destroyed ship yet and the cells of destroyed
+/// ships.
+#[derive(Clone, PartialEq, Debug)]
+pub struct FleetTracker {
+    remaining: Vec<Dimension>,
+    hits: Vec<(Dimension, Dimension)>,
+    sunk: Vec<(Dimension, Dimension)>,
+}
+
+impl FleetTracker {
+    /// Creates a new instance tracking the ship types of `fleet`. Decoys and submerged ships,
+    /// which shots can't reach, are ignored.
+    pub fn new<T: ShipTypeContainer>(fleet: &T) -> Self {
+        FleetTracker {
+            remaining: fleet.ship_types().iter()
+                .filter(|ship_type| !ship_type.is_decoy() && ship_type.layer() == Layer::Surface)
+                .map(|ship_type| ship_type.length())
+                .collect(),
+            hits: Vec::new(),
+            sunk: Vec::new(),
+        }
+    }
+
+    pub fn remaining(&self) -> &[Dimension] {
+        &self.remaining
+    }
+
+    pub fn hits(&self) -> &[(Dimension, Dimension)] {
+        &self.hits
+    }
+
+    pub fn sunk(&self) -> &[(Dimension, Dimension)] {
+        &self.sunk
+    }
+
+    pub fn record_result(
+        &mut self,
+        x: Dimension,
+        y: Dimension,
+        result: ShootOk,
+    ) {
+        match result {
+            ShootOk::Hit | ShootOk::Armored => self.record_hit(x, y),
+            ShootOk::Destroyed | ShootOk::WinningShot => {
+                self.record_hit(x, y);
+                self.record_destroyed(x, y);
+            },
+            ShootOk::Miss | ShootOk::Mine => {},
+        }
+    }
+
+    /// Adds (`x`, `y`) to the pending hits, unless it's already there, e.g. because the armor
+    /// of the segment held before.
+    fn record_hit(
+        &mut self,
+        x: Dimension,
+        y: Dimension,
+    ) {
+        if !self.hits.contains(&(x, y)) {
+            self.hits.push((x, y));
+        }
+    }
+
+    /// Moves the destroyed ship around (`x`, `y`) from the pending hits to the sunk cells and
+    /// removes it from the fleet. The ship is assumed to be the longest line of pending hits
+    /// through (`x`, `y`).
+    fn record_destroyed(
+        &mut self,
+        x: Dimension,
+        y: Dimension,
+    ) {
+        let hits: HashSet<(Dimension, Dimension)> = self.hits.iter().cloned().collect();
+        let ship = DIRECTIONS.iter()
+            .map(|&(dx, dy)| {
+                let mut line = vec!((x, y));
+                for &sign in &[1, -1] {
+                    let (mut cx, mut cy) = (x as isize, y as isize);
+                    loop {
+                        cx += sign * dx;
+                        cy += sign * dy;
+                        if cx < 0 || cy < 0 || !hits.contains(&(cx as Dimension, cy as Dimension)) {
+                            break;
+                        }
+                        line.push((cx as Dimension, cy as Dimension));
+                    }
+                }
+                line
+            })
+            .max_by_key(|line| line.len())
+            .unwrap();
+
+        self.hits.retain(|cell| !ship.contains(cell));
+        let sunk = self.remaining.iter().cloned()
+            .filter(|&length| length <= ship.len())
+            .max()
+            .or_else(|| self.remaining.iter().cloned().min());
+        if let Some(sunk) = sunk {
+            let index = self.remaining.iter().position(|&length| length == sunk).unwrap();
+            self.remaining.remove(index);
+        }
+        self.sunk.extend(ship);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::Layer;
+    use common::ShipType;
+    use common::ShipTypeContainer;
+    use results::ShootOk::*;
+    use std::sync::Arc;
+    use super::FleetTracker;
+
+    struct Fleet;
+
+    impl ShipTypeContainer for Fleet {
+        fn ship_types(&self) -> Vec<Arc<ShipType>> {
+            vec!(
+                Arc::new(ShipType::new(0, "Cruiser", 3)),
+                Arc::new(ShipType::new(1, "Corvette", 2)),
+                Arc::new(ShipType::new(2, "Decoy", 2).with_decoy(true)),
+                Arc::new(ShipType::new(3, "Submarine", 4).with_layer(Layer::Submerged)),
+            )
+        }
+    }
+
+    #[test]
+    fn should_track_destroyed_ships() {
+        let mut tracker = FleetTracker::new(&Fleet);
+        assert_eq!(&[3, 2], tracker.remaining());
+
+        tracker.record_result(0, 0, Hit);
+        tracker.record_result(3, 3, Hit);
+        tracker.record_result(1, 0, Miss);
+        tracker.record_result(0, 1, Destroyed);
+
+        assert_eq!(&[3], tracker.remaining());
+        assert_eq!(&[(3, 3)], tracker.hits());
+        assert_eq!(&[(0, 1), (0, 0)], tracker.sunk());
+    }
+
+    #[test]
+    fn should_keep_armored_cells_pending() {
+        let mut tracker = FleetTracker::new(&Fleet);
+
+        tracker.record_result(0, 0, Armored);
+        assert_eq!(&[(0, 0)], tracker.hits());
+        tracker.record_result(0, 0, Hit);
+        tracker.record_result(1, 0, Destroyed);
+        assert!(tracker.hits().is_empty());
+        assert_eq!(&[3], tracker.remaining());
+    }
+}
@@ -0,0 +1,249 @@
+use ai::fleet_tracker::DIRECTIONS;
+use ai::fleet_tracker::FleetTracker;
+use ai::Shooter;
+use ai::View;
+use common::CellStatus;
+use common::Dimensional;
+use common::ShipTypeContainer;
+use random::Rng;
+use results::ShootOk;
+use std::collections::HashSet;
+use ::Dimension;
+
+/// A bot of medium strength. While hunting, it shoots at a checkerboard pattern spaced by the
+/// length of the shortest ship still afloat. After a hit, it targets the cells around the hit
+/// until the ship is destroyed, then it goes back to hunting.
+#[derive(Clone, PartialEq, Debug)]
+pub struct HuntTargetBot {
+    rng: Rng,
+    fleet: FleetTracker,
+    fired: HashSet<(Dimension, Dimension)>,
+}
+
+impl HuntTargetBot {
+    /// Creates a new instance hunting the ship types of `fleet`, e.g. a `PreGame` or a `Game`.
+    /// Decoys and submerged ships are ignored. Bots with the same seed pick the same cells.
+    pub fn new<T: ShipTypeContainer>(
+        fleet: &T,
+        seed: u64,
+    ) -> Self {
+        HuntTargetBot {
+            rng: Rng::new(seed),
+            fleet: FleetTracker::new(fleet),
+            fired: HashSet::new(),
+        }
+    }
+
+    /// Returns the lengths of the ships the bot believes to be still afloat.
+    pub fn remaining_ships(&self) -> &[Dimension] {
+        self.fleet.remaining()
+    }
+
+    fn is_candidate(
+        &self,
+        view: &View,
+        cell: (Dimension, Dimension),
+    ) -> bool {
+        view.is_unknown(cell.0, cell.1) && !self.fired.contains(&cell)
+    }
+
+    /// Returns the hits whose armor held or, if there are none, the unknown cells extending a
+    /// line of hits or, if there are none either, the unknown cells next to a hit.
+    fn targets(
+        &self,
+        view: &View,
+    ) -> Vec<(Dimension, Dimension)> {
+        let armored: Vec<(Dimension, Dimension)> = self.fleet.hits().iter().cloned()
+            .filter(|&(x, y)| view.get(x, y) == CellStatus::Armored && self.is_candidate(view, (x, y)))
+            .collect();
+        if !armored.is_empty() {
+            return armored;
+        }
+        let hits: HashSet<(Dimension, Dimension)> = self.fleet.hits().iter().cloned().collect();
+        let mut line_ends = Vec::new();
+        let mut neighbours = Vec::new();
+        for &(x, y) in self.fleet.hits() {
+            for &(dx, dy) in DIRECTIONS.iter() {
+                let forward = offset(view, x, y, dx, dy);
+                let backward = offset(view, x, y, -dx, -dy);
+                let in_line = forward.is_some_and(|cell| hits.contains(&cell))
+                    || backward.is_some_and(|cell| hits.contains(&cell));
+                for cell in forward.into_iter().chain(backward) {
+                    if self.is_candidate(view, cell) {
+                        if in_line {
+                            line_ends.push(cell);
+                        } else {
+                            neighbours.push(cell);
+                        }
+                    }
+                }
+            }
+        }
+        if line_ends.is_empty() { neighbours } else { line_ends }
+    }
+
+    fn hunting_cells(
+        &self,
+        view: &View,
+    ) -> Vec<(Dimension, Dimension)> {
+        let shortest = self.fleet.remaining().iter().cloned().min().unwrap_or(1);
+        let candidates: Vec<(Dimension, Dimension)> = view.unknown_cells().into_iter()
+            .filter(|&cell| self.is_candidate(view, cell))
+            .collect();
+        let fitting: Vec<(Dimension, Dimension)> = candidates.iter().cloned()
+            .filter(|&(x, y)| fits(view, x, y, shortest))
+            .collect();
+        let parity: Vec<(Dimension, Dimension)> = fitting.iter().cloned()
+            .filter(|&(x, y)| (x + y) % shortest == 0)
+            .collect();
+
+        if !parity.is_empty() {
+            parity
+        } else if !fitting.is_empty() {
+            fitting
+        } else {
+            candidates
+        }
+    }
+}
+
+impl Shooter for HuntTargetBot {
+    fn next_shot(
+        &mut self,
+        view: &View,
+    ) -> Option<(Dimension, Dimension)> {
+        let mut candidates = self.targets(view);
+        if candidates.is_empty() {
+            candidates = self.hunting_cells(view);
+        }
+        if candidates.is_empty() {
+            return None;
+        }
+        let target = candidates[self.rng.below(candidates.len())];
+        self.fired.insert(target);
+        Some(target)
+    }
+
+    fn record_result(
+        &mut self,
+        x: Dimension,
+        y: Dimension,
+        result: ShootOk,
+    ) {
+        if result == ShootOk::Armored {
+            self.fired.remove(&(x, y));
+        }
+        self.fleet.record_result(x, y, result);
+    }
+}
+
+fn offset(
+    view: &View,
+    x: Dimension,
+    y: Dimension,
+    dx: isize,
+    dy: isize,
+) -> Option<(Dimension, Dimension)> {
+    let (nx, ny) = (x as isize + dx, y as isize + dy);
+    if nx < 0 || ny < 0 || nx >= view.width() as isize || ny >= view.height() as isize {
+        None
+    } else {
+        Some((nx as Dimension, ny as Dimension))
+    }
+}
+
+/// Returns whether a ship of `length` could lie on (`x`, `y`), judging by the unknown cells
+/// around it.
+fn fits(
+    view: &View,
+    x: Dimension,
+    y: Dimension,
+    length: Dimension,
+) -> bool {
+    DIRECTIONS.iter().any(|&(dx, dy)| {
+        let mut run = 1;
+        for &sign in &[1, -1] {
+            let mut cell = (x, y);
+            while let Some(next) = offset(view, cell.0, cell.1, sign * dx, sign * dy) {
+                if !view.is_unknown(next.0, next.1) {
+                    break;
+                }
+                run += 1;
+                cell = next;
+            }
+        }
+        run >= length
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use ai::Shooter;
+    use ai::View;
+    use ai::test_util::play_against_idle;
+    use common::CellStatus;
+    use common::Orientation::*;
+    use common::Player::*;
+    use pregame::PreGame;
+    use results::ShootOk::*;
+    use super::HuntTargetBot;
+
+    fn build_test_pregame() -> PreGame {
+        let mut pregame = PreGame::new(6, 6).unwrap();
+        let cruiser = pregame.add_ship_type("Cruiser", 3).unwrap();
+        let corvette = pregame.add_ship_type("Corvette", 2).unwrap();
+        for &player in &[P1, P2] {
+            pregame.place_ship(player, &cruiser, 1, 1, Vertical).unwrap();
+            pregame.place_ship(player, &corvette, 3, 4, Horizontal).unwrap();
+        }
+
+        pregame
+    }
+
+    #[test]
+    fn should_hunt_on_parity_cells() {
+        let mut bot = HuntTargetBot::new(&build_test_pregame(), 1);
+        let view = View::new(6, 6);
+
+        for _ in 0..18 {
+            let (x, y) = bot.next_shot(&view).unwrap();
+            assert_eq!(0, (x + y) % 2);
+        }
+    }
+
+    #[test]
+    fn should_target_around_hits_until_destroyed() {
+        let pregame = build_test_pregame();
+        let mut bot = HuntTargetBot::new(&pregame, 1);
+        let mut view = View::new(6, 6);
+
+        bot.record_result(1, 2, Hit);
+        view.set(1, 2, CellStatus::Hit);
+        let (x, y) = bot.next_shot(&view).unwrap();
+        assert!([(0, 2), (2, 2), (1, 1), (1, 3)].contains(&(x, y)));
+
+        bot.record_result(1, 1, Hit);
+        view.set(1, 1, CellStatus::Hit);
+        let (x, y) = bot.next_shot(&view).unwrap();
+        assert!([(1, 0), (1, 3)].contains(&(x, y)));
+
+        bot.record_result(1, 3, Destroyed);
+        assert_eq!(&[2], bot.remaining_ships());
+        view.set(1, 3, CellStatus::Hit);
+        view.set(1, 0, CellStatus::Miss);
+        let (x, y) = bot.next_shot(&view).unwrap();
+        assert_eq!(0, (x + y) % 2);
+    }
+
+    #[test]
+    fn should_sink_a_fleet() {
+        let pregame = build_test_pregame();
+        let mut bot = HuntTargetBot::new(&pregame, 7);
+        let mut game = pregame.start().unwrap();
+
+        let shots = play_against_idle(&mut game, &mut bot, 0, 5);
+        assert_eq!(Some(P1), game.get_winner());
+        assert!(shots.len() < 36);
+        assert!(bot.remaining_ships().is_empty());
+    }
+}
@@ -0,0 +1,44 @@
+//! Computer opponents and the building blocks to write your own.
+//!
+//! ```
+//! # use lib_battleship::common::Orientation::Horizontal;
+//! # use lib_battleship::common::Player::{P1, P2};
+//! # use lib_battleship::PreGame;
+//! use lib_battleship::ai::{RandomBot, Shooter, View};
+//!
+//! # let mut pregame = PreGame::new(3, 3).unwrap();
+//! # let corvette = pregame.add_ship_type("Corvette", 2).unwrap();
+//! # pregame.place_ship(P1, &corvette, 0, 0, Horizontal).unwrap();
+//! # pregame.place_ship(P2, &corvette, 0, 0, Horizontal).unwrap();
+//! # let mut game = pregame.start().unwrap();
+//! let mut bot = RandomBot::new(42);
+//! let (x, y) = bot.next_shot(&View::of_opponent(&game, P1)).unwrap();
+//! let result = game.shoot(P2, x, y).unwrap();
+//! bot.record_result(x, y, result);
+//! ```
+
+pub use self::adaptive_bot::AdaptiveBot;
+pub use self::difficulty::Difficulty;
+pub use self::hunt_target_bot::HuntTargetBot;
+pub use self::placement::Placement;
+pub use self::placement::PlacementStrategy;
+pub use self::placement_history::PlacementHistory;
+pub use self::probability_bot::ProbabilityBot;
+pub use self::random_bot::RandomBot;
+pub use self::shooter::Shooter;
+pub use self::tunable_bot::TunableBot;
+pub use self::view::View;
+
+mod adaptive_bot;
+mod difficulty;
+pub(crate) mod fleet_tracker;
+mod hunt_target_bot;
+mod placement;
+mod placement_history;
+mod probability_bot;
+mod random_bot;
+mod shooter;
+#[cfg(test)]
+mod test_util;
+mod tunable_bot;
+mod view;
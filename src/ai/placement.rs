@@ -0,0 +1,259 @@
+use ai::View;
+use analysis::probability_map;
+use battlefield::Battlefield;
+use common::Dimensional;
+use common::Orientation;
+use common::Player;
+use common::ShipType;
+use common::ShipTypeContainer;
+use pregame::PreGame;
+use random::Rng;
+use results::PlaceError;
+use std::cmp::min;
+use std::cmp::Reverse;
+use std::sync::Arc;
+use ::Dimension;
+
+/// How often a strategy starts over when the ships it already placed leave no room for the
+/// next one.
+const ATTEMPTS: usize = 20;
+
+/// A ship position chosen by a `PlacementStrategy`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Placement {
+    ship_type: Arc<ShipType>,
+    x: Dimension,
+    y: Dimension,
+    orientation: Orientation,
+}
+
+impl Placement {
+    /// Creates a new instance.
+    pub fn new(
+        ship_type: Arc<ShipType>,
+        x: Dimension,
+        y: Dimension,
+        orientation: Orientation,
+    ) -> Self {
+        Placement {
+            ship_type,
+            x,
+            y,
+            orientation,
+        }
+    }
+
+    /// Returns the type of the ship to place.
+    pub fn ship_type(&self) -> &Arc<ShipType> {
+        &self.ship_type
+    }
+
+    /// Returns the x coordinate of the ship's first cell.
+    pub fn x(&self) -> Dimension {
+        self.x
+    }
+
+    /// Returns the y coordinate of the ship's first cell.
+    pub fn y(&self) -> Dimension {
+        self.y
+    }
+
+    /// Returns the orientation of the ship.
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    /// Places the ship for `player`, see `PreGame::place_ship`.
+    pub fn apply(
+        &self,
+        pregame: &mut PreGame,
+        player: Player,
+    ) -> Result<(), PlaceError> {
+        pregame.place_ship(player, &self.ship_type, self.x, self.y, self.orientation)
+    }
+}
+
+/// Ways for a bot to lay out its fleet.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum PlacementStrategy {
+    /// Every free position is equally likely.
+    Random,
+    /// Ships are put along the borders of the battlefield.
+    EdgeHugging,
+    /// Ships are kept as far away from each other as possible.
+    SpreadOut,
+    /// Ships are put where `analysis::probability_map` expects them the least, which is where
+    /// density-based hunters like `ProbabilityBot` look last.
+    AntiHeatmap,
+}
+
+impl PlacementStrategy {
+    /// Chooses positions for all ships `player` hasn't placed yet on `pregame`, longest ships
+    /// first. Positions are picked at random among the best ones for the strategy (the best
+    /// quarter for `AntiHeatmap`), so the layout isn't predictable, but the same seed always
+    /// leads to the same layout. Returns `None` if the strategy couldn't fit the fleet.
+    pub fn place(
+        &self,
+        pregame: &PreGame,
+        player: Player,
+        seed: u64,
+    ) -> Option<Vec<Placement>> {
+        let battlefield = pregame.battlefield(player);
+        let mut ship_types: Vec<Arc<ShipType>> = pregame.ship_types().into_iter()
+            .filter(|ship_type| battlefield.ship_position(ship_type.id()).is_none())
+            .collect();
+        ship_types.sort_by_key(|ship_type| Reverse(ship_type.length()));
+        let lengths: Vec<Dimension> = pregame.ship_types().iter()
+            .filter(|ship_type| !ship_type.is_decoy())
+            .map(|ship_type| ship_type.length())
+            .collect();
+        let density = probability_map(&View::new(pregame.width(), pregame.height()), &lengths);
+
+        let mut rng = Rng::new(seed);
+        for _ in 0..ATTEMPTS {
+            let mut bf = battlefield.clone();
+            let mut placements = Vec::new();
+            for ship_type in &ship_types {
+                let mut candidates: Vec<_> = bf
+                    .free_positions(ship_type.length(), ship_type.layer()).into_iter()
+                    .map(|position| {
+                        let coords = bf.ship_coords(ship_type.length(), position).unwrap();
+                        let score = match *self {
+                            PlacementStrategy::Random => 0.0,
+                            PlacementStrategy::EdgeHugging => edge_cells(&bf, &coords) as f64,
+                            PlacementStrategy::SpreadOut => distance(&bf, pregame, &coords) as f64,
+                            PlacementStrategy::AntiHeatmap => -coords.iter()
+                                .map(|&(x, y)| density.get(x, y))
+                                .sum::<f64>(),
+                        };
+                        (score, position, coords)
+                    })
+                    .collect();
+                if candidates.is_empty() {
+                    break;
+                }
+                candidates.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap());
+                let top = candidates[0].0;
+                let best = match *self {
+                    PlacementStrategy::Random => candidates.len(),
+                    PlacementStrategy::AntiHeatmap => candidates.len().div_ceil(4),
+                    _ => candidates.iter().take_while(|candidate| candidate.0 == top).count(),
+                };
+                let (_, position, coords) = candidates.swap_remove(rng.below(best));
+                bf.place_ship(ship_type.id(), ship_type.layer(), position, &coords);
+                let (x, y, orientation) = position;
+                placements.push(Placement::new(ship_type.clone(), x, y, orientation));
+            }
+            if placements.len() == ship_types.len() {
+                return Some(placements);
+            }
+        }
+        None
+    }
+}
+
+fn edge_cells(
+    bf: &Battlefield,
+    coords: &[(Dimension, Dimension)],
+) -> usize {
+    coords.iter()
+        .filter(|&&(x, y)| x == 0 || y == 0 || x + 1 == bf.width() || y + 1 == bf.height())
+        .count()
+}
+
+/// Returns the distance of `coords` to the closest ship on `bf`, counting diagonal steps as
+/// one. Returns the size of the battlefield if there are no ships yet.
+fn distance(
+    bf: &Battlefield,
+    pregame: &PreGame,
+    coords: &[(Dimension, Dimension)],
+) -> Dimension {
+    let mut closest = bf.width() + bf.height();
+    for ship_type in pregame.ship_types() {
+        for (sx, sy) in bf.ship_cells(ship_type.id()) {
+            for &(x, y) in coords {
+                closest = min(closest, x.abs_diff(sx).max(y.abs_diff(sy)));
+            }
+        }
+    }
+    closest
+}
+
+#[cfg(test)]
+mod test {
+    use common::Orientation::*;
+    use common::ShipTypeContainer;
+    use common::Player::*;
+    use pregame::PreGame;
+    use super::PlacementStrategy;
+    use super::PlacementStrategy::*;
+
+    fn build_test_pregame() -> PreGame {
+        let mut pregame = PreGame::new(8, 8).unwrap();
+        pregame.add_ship_type("Battleship", 4).unwrap();
+        pregame.add_ship_type("Cruiser", 3).unwrap();
+        pregame.add_ship_type("Destroyer", 3).unwrap();
+        pregame.add_ship_type("Corvette", 2).unwrap();
+
+        pregame
+    }
+
+    #[test]
+    fn should_place_whole_fleets() {
+        for &strategy in &[Random, EdgeHugging, SpreadOut, AntiHeatmap] {
+            let mut pregame = build_test_pregame();
+            for &player in &[P1, P2] {
+                let placements = strategy.place(&pregame, player, 3).unwrap();
+                assert_eq!(4, placements.len());
+                for placement in placements {
+                    placement.apply(&mut pregame, player).unwrap();
+                }
+            }
+            assert!(pregame.start().is_ok());
+        }
+    }
+
+    #[test]
+    fn should_only_place_missing_ships_reproducibly() {
+        let mut pregame = build_test_pregame();
+        let battleship = pregame.ship_types()[0].clone();
+        pregame.place_ship(P1, &battleship, 2, 2, Vertical).unwrap();
+
+        let placements = SpreadOut.place(&pregame, P1, 5).unwrap();
+        assert_eq!(3, placements.len());
+        assert!(placements.iter().all(|placement| placement.ship_type() != &battleship));
+        assert_eq!(Some(placements), SpreadOut.place(&pregame, P1, 5));
+    }
+
+    #[test]
+    fn should_follow_the_strategy() {
+        let pregame = build_test_pregame();
+        let on_edge = |x, y| x == 0 || y == 0 || x == 7 || y == 7;
+        let in_centre = |x, y| (2..6).contains(&x) && (2..6).contains(&y);
+        let cells = |strategy: PlacementStrategy, seed| {
+            let placements = strategy.place(&pregame, P1, seed).unwrap();
+            placements.into_iter()
+                .flat_map(|placement| {
+                    let (dx, dy) = if placement.orientation() == Horizontal { (1, 0) } else { (0, 1) };
+                    (0..placement.ship_type().length())
+                        .map(move |n| (placement.x() + n * dx, placement.y() + n * dy))
+                })
+                .collect::<Vec<_>>()
+        };
+
+        for seed in 0..5 {
+            assert!(cells(EdgeHugging, seed).into_iter().all(|(x, y)| on_edge(x, y)));
+            assert!(cells(AntiHeatmap, seed).into_iter().all(|(x, y)| !in_centre(x, y)));
+        }
+    }
+
+    #[test]
+    fn should_give_up_when_the_fleet_does_not_fit() {
+        let mut pregame = PreGame::new(2, 2).unwrap();
+        pregame.add_ship_type("Corvette", 2).unwrap();
+        pregame.add_ship_type("Frigate", 2).unwrap();
+        pregame.add_ship_type("Submarine", 1).unwrap();
+
+        assert_eq!(None, Random.place(&pregame, P1, 1));
+    }
+}
@@ -0,0 +1,200 @@
+use common::CellStatus;
+use common::Dimensional;
+use common::Player;
+use game::Game;
+use results::HistoryError;
+use results::HistoryParseError;
+use ::Dimension;
+
+/// Where an opponent put their ships in past games, to be used by an `AdaptiveBot`. Keep one
+/// history per opponent and save it with `to_text` to carry it across sessions.
+///
+/// # Text format
+/// The size of the battlefield, the number of games and then, one line per row, how many
+/// times each cell held a ship:
+///
+/// ```text
+/// size: 3 2
+/// games: 4
+/// 4 4 0
+/// 0 1 2
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct PlacementHistory {
+    games: usize,
+    counts: Vec<Vec<usize>>,
+}
+
+impl PlacementHistory {
+    /// Creates a new, empty history for battlefields of the given size.
+    ///
+    /// # Panics
+    /// Panics if `width` or `height` is 0.
+    pub fn new(
+        width: Dimension,
+        height: Dimension,
+    ) -> Self {
+        assert!(width > 0 && height > 0, "dimensions must not be zero");
+        PlacementHistory {
+            games: 0,
+            counts: vec!(vec!(0; width); height),
+        }
+    }
+
+    /// Records the ship cells of `opponent` at the end of `game`.
+    /// # Errors
+    /// * `SizeMismatch` if the game's battlefield doesn't have the history's size
+    /// * `GameNotOver` if the game is still running
+    pub fn record_game(
+        &mut self,
+        game: &Game,
+        opponent: Player,
+    ) -> Result<(), HistoryError> {
+        if (game.width(), game.height()) != (self.width(), self.height()) {
+            return Err(HistoryError::SizeMismatch);
+        }
+        if game.outcome().is_none() {
+            return Err(HistoryError::GameNotOver);
+        }
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                match game.get_cell(opponent, x, y) {
+                    CellStatus::Ship | CellStatus::Hit | CellStatus::Armored => self.counts[y][x] += 1,
+                    _ => {},
+                }
+            }
+        }
+        self.games += 1;
+        Ok(())
+    }
+
+    /// Returns the number of games recorded.
+    pub fn games(&self) -> usize {
+        self.games
+    }
+
+    /// Returns the share of games in which the cell (`x`, `y`) held a ship, between 0 and 1.
+    ///
+    /// # Panics
+    /// Panics if the x and/or y coordinate is out of bounds.
+    pub fn frequency(
+        &self,
+        x: Dimension,
+        y: Dimension,
+    ) -> f64 {
+        if self.games == 0 {
+            0.0
+        } else {
+            self.counts[y][x] as f64 / self.games as f64
+        }
+    }
+
+    /// Exports the history in the text format described above.
+    pub fn to_text(&self) -> String {
+        let mut text = format!("size: {} {}\ngames: {}\n", self.width(), self.height(), self.games);
+        for row in &self.counts {
+            let row: Vec<String> = row.iter().map(|count| count.to_string()).collect();
+            text.push_str(&row.join(" "));
+            text.push('\n');
+        }
+        text
+    }
+
+    /// Imports a history in the text format described above.
+    /// # Errors
+    /// * `MissingLine` if a line is missing or doesn't start with the expected label
+    /// * `IllegalNumber` if a number can't be read, or a cell was counted in more games than
+    ///   recorded
+    /// * `SizeMismatch` if the rows don't match the size, or the size is 0
+    pub fn from_text(text: &str) -> Result<PlacementHistory, HistoryParseError> {
+        let mut lines = text.lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty());
+
+        let size = parse_numbers(lines.next(), "size:")?;
+        let games = parse_numbers(lines.next(), "games:")?;
+        if size.len() != 2 || games.len() != 1 {
+            return Err(HistoryParseError::IllegalNumber);
+        }
+        let counts = lines
+            .map(|line| parse_numbers(Some(line), ""))
+            .collect::<Result<Vec<Vec<usize>>, HistoryParseError>>()?;
+        if size.contains(&0) || counts.len() != size[1] || counts.iter().any(|row| row.len() != size[0]) {
+            return Err(HistoryParseError::SizeMismatch);
+        }
+        if counts.iter().flat_map(|row| row.iter()).any(|&count| count > games[0]) {
+            return Err(HistoryParseError::IllegalNumber);
+        }
+
+        Ok(PlacementHistory {
+            games: games[0],
+            counts,
+        })
+    }
+}
+
+impl Dimensional for PlacementHistory {
+    fn width(&self) -> Dimension {
+        self.counts.first().unwrap().len()
+    }
+
+    fn height(&self) -> Dimension {
+        self.counts.len()
+    }
+}
+
+fn parse_numbers(
+    line: Option<&str>,
+    label: &str,
+) -> Result<Vec<usize>, HistoryParseError> {
+    let line = line.ok_or(HistoryParseError::MissingLine)?;
+    if !line.starts_with(label) {
+        return Err(HistoryParseError::MissingLine);
+    }
+    line[label.len()..].split_whitespace()
+        .map(|number| number.parse().map_err(|_| HistoryParseError::IllegalNumber))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use common::Orientation::*;
+    use common::Player::*;
+    use pregame::PreGame;
+    use results::HistoryError;
+    use results::HistoryParseError;
+    use super::PlacementHistory;
+
+    #[test]
+    fn should_record_finished_games() {
+        let mut pregame = PreGame::new(3, 2).unwrap();
+        let corvette = pregame.add_ship_type("Corvette", 2).unwrap();
+        pregame.place_ship(P1, &corvette, 0, 0, Horizontal).unwrap();
+        pregame.place_ship(P2, &corvette, 1, 0, Vertical).unwrap();
+        let mut game = pregame.start().unwrap();
+
+        let mut history = PlacementHistory::new(3, 2);
+        assert_eq!(Err(HistoryError::SizeMismatch), PlacementHistory::new(2, 2).record_game(&game, P2));
+        assert_eq!(Err(HistoryError::GameNotOver), history.record_game(&game, P2));
+        game.shoot(P2, 1, 0).unwrap();
+        game.shoot(P2, 1, 1).unwrap();
+        history.record_game(&game, P2).unwrap();
+        history.record_game(&game, P1).unwrap();
+
+        assert_eq!(2, history.games());
+        assert_eq!(1.0, history.frequency(1, 0));
+        assert_eq!(0.5, history.frequency(0, 0));
+        assert_eq!(0.5, history.frequency(1, 1));
+        assert_eq!(0.0, history.frequency(2, 1));
+        assert_eq!("size: 3 2\ngames: 2\n1 2 0\n0 1 0\n", history.to_text());
+        assert_eq!(Ok(history.clone()), PlacementHistory::from_text(&history.to_text()));
+    }
+
+    #[test]
+    fn should_reject_broken_text() {
+        assert_eq!(Err(HistoryParseError::MissingLine), PlacementHistory::from_text("games: 1\n0 0\n"));
+        assert_eq!(Err(HistoryParseError::IllegalNumber), PlacementHistory::from_text("size: 2 1\ngames: x\n0 0\n"));
+        assert_eq!(Err(HistoryParseError::IllegalNumber), PlacementHistory::from_text("size: 2 1\ngames: 1\n0 2\n"));
+        assert_eq!(Err(HistoryParseError::SizeMismatch), PlacementHistory::from_text("size: 2 2\ngames: 1\n0 0\n"));
+    }
+}
@@ -0,0 +1,131 @@
+use ai::fleet_tracker::FleetTracker;
+use ai::Shooter;
+use ai::View;
+use analysis::probability_map;
+use common::CellStatus;
+use common::ShipTypeContainer;
+use random::Rng;
+use results::ShootOk;
+use std::collections::HashSet;
+use ::Dimension;
+
+/// A strong bot, always shooting at the cell most likely to hold a ship segment according to
+/// `analysis::probability_map`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ProbabilityBot {
+    rng: Rng,
+    fleet: FleetTracker,
+    fired: HashSet<(Dimension, Dimension)>,
+}
+
+impl ProbabilityBot {
+    /// Creates a new instance hunting the ship types of `fleet`, e.g. a `PreGame` or a `Game`.
+    /// Decoys and submerged ships are ignored. Bots with the same seed break ties between
+    /// equally likely cells the same way.
+    pub fn new<T: ShipTypeContainer>(
+        fleet: &T,
+        seed: u64,
+    ) -> Self {
+        ProbabilityBot {
+            rng: Rng::new(seed),
+            fleet: FleetTracker::new(fleet),
+            fired: HashSet::new(),
+        }
+    }
+
+    /// Returns the lengths of the ships the bot believes to be still afloat.
+    pub fn remaining_ships(&self) -> &[Dimension] {
+        self.fleet.remaining()
+    }
+}
+
+impl Shooter for ProbabilityBot {
+    fn next_shot(
+        &mut self,
+        view: &View,
+    ) -> Option<(Dimension, Dimension)> {
+        let mut view = view.clone();
+        for &(x, y) in self.fleet.sunk() {
+            view.set(x, y, CellStatus::Miss);
+        }
+
+        let map = probability_map(&view, self.fleet.remaining());
+        let candidates = map.best_cells(|x, y| view.is_unknown(x, y) && !self.fired.contains(&(x, y)));
+        if candidates.is_empty() {
+            return None;
+        }
+        let target = candidates[self.rng.below(candidates.len())];
+        self.fired.insert(target);
+        Some(target)
+    }
+
+    fn record_result(
+        &mut self,
+        x: Dimension,
+        y: Dimension,
+        result: ShootOk,
+    ) {
+        if result == ShootOk::Armored {
+            self.fired.remove(&(x, y));
+        }
+        self.fleet.record_result(x, y, result);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ai::HuntTargetBot;
+    use ai::RandomBot;
+    use ai::Shooter;
+    use ai::View;
+    use ai::test_util::play_against_idle;
+    use common::CellStatus;
+    use common::Orientation::*;
+    use common::Player::*;
+    use pregame::PreGame;
+    use results::ShootOk::*;
+    use super::ProbabilityBot;
+
+    fn build_test_pregame() -> PreGame {
+        let mut pregame = PreGame::new(8, 8).unwrap();
+        let battleship = pregame.add_ship_type("Battleship", 4).unwrap();
+        let cruiser = pregame.add_ship_type("Cruiser", 3).unwrap();
+        let corvette = pregame.add_ship_type("Corvette", 2).unwrap();
+        pregame.place_ship(P1, &battleship, 0, 0, Horizontal).unwrap();
+        pregame.place_ship(P1, &cruiser, 0, 1, Horizontal).unwrap();
+        pregame.place_ship(P1, &corvette, 0, 2, Horizontal).unwrap();
+        pregame.place_ship(P2, &battleship, 6, 2, Vertical).unwrap();
+        pregame.place_ship(P2, &cruiser, 1, 5, Horizontal).unwrap();
+        pregame.place_ship(P2, &corvette, 3, 1, Vertical).unwrap();
+
+        pregame
+    }
+
+    fn shots_to_win<S: Shooter>(mut bot: S) -> usize {
+        let mut game = build_test_pregame().start().unwrap();
+        play_against_idle(&mut game, &mut bot, 7, 7).len()
+    }
+
+    #[test]
+    fn should_prefer_cells_next_to_hits() {
+        let fleet = build_test_pregame();
+        let mut bot = ProbabilityBot::new(&fleet, 1);
+        let mut view = View::new(8, 8);
+
+        bot.record_result(4, 4, Hit);
+        view.set(4, 4, CellStatus::Hit);
+        let (x, y) = bot.next_shot(&view).unwrap();
+        assert!([(3, 4), (5, 4), (4, 3), (4, 5)].contains(&(x, y)));
+    }
+
+    #[test]
+    fn should_beat_weaker_bots() {
+        let fleet = build_test_pregame();
+        let expert: usize = (0..5).map(|seed| shots_to_win(ProbabilityBot::new(&fleet, seed))).sum();
+        let medium: usize = (0..5).map(|seed| shots_to_win(HuntTargetBot::new(&fleet, seed))).sum();
+        let random: usize = (0..5).map(|seed| shots_to_win(RandomBot::new(seed))).sum();
+
+        assert!(expert < medium);
+        assert!(medium < random);
+    }
+}
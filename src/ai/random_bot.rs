@@ -0,0 +1,72 @@
+use ai::Shooter;
+use ai::View;
+use random::Rng;
+use results::ShootOk;
+use std::collections::HashSet;
+use ::Dimension;
+
+/// A bot shooting at random cells, never shooting at the same cell twice unless its armor held.
+#[derive(Clone, PartialEq, Debug)]
+pub struct RandomBot {
+    rng: Rng,
+    fired: HashSet<(Dimension, Dimension)>,
+}
+
+impl RandomBot {
+    /// Creates a new instance. Bots with the same seed pick the same cells.
+    pub fn new(seed: u64) -> Self {
+        RandomBot {
+            rng: Rng::new(seed),
+            fired: HashSet::new(),
+        }
+    }
+}
+
+impl Shooter for RandomBot {
+    fn next_shot(
+        &mut self,
+        view: &View,
+    ) -> Option<(Dimension, Dimension)> {
+        let candidates: Vec<(Dimension, Dimension)> = view.unknown_cells().into_iter()
+            .filter(|cell| !self.fired.contains(cell))
+            .collect();
+        if candidates.is_empty() {
+            return None;
+        }
+        let target = candidates[self.rng.below(candidates.len())];
+        self.fired.insert(target);
+        Some(target)
+    }
+
+    fn record_result(
+        &mut self,
+        x: Dimension,
+        y: Dimension,
+        result: ShootOk,
+    ) {
+        if result == ShootOk::Armored {
+            self.fired.remove(&(x, y));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ai::Shooter;
+    use ai::View;
+    use common::CellStatus;
+    use std::collections::HashSet;
+    use super::RandomBot;
+
+    #[test]
+    fn should_never_repeat_a_cell() {
+        let mut bot = RandomBot::new(5);
+        let mut view = View::new(3, 3);
+        view.set(1, 1, CellStatus::Miss);
+
+        let shots: HashSet<_> = (0..8).map(|_| bot.next_shot(&view).unwrap()).collect();
+        assert_eq!(8, shots.len());
+        assert!(!shots.contains(&(1, 1)));
+        assert_eq!(None, bot.next_shot(&view));
+    }
+}
@@ -0,0 +1,41 @@
+use ai::View;
+use results::ShootOk;
+use results::Verdict;
+use ::Dimension;
+
+/// A bot that decides where to shoot next.
+pub trait Shooter {
+    /// Picks the next cell to shoot at, given what its player knows about the opponent's
+    /// battlefield. Returns `None` if there's nothing left to shoot at.
+    fn next_shot(
+        &mut self,
+        view: &View,
+    ) -> Option<(Dimension, Dimension)>;
+
+    /// Tells the bot the result of a shot at (`x`, `y`). Bots that don't learn from their
+    /// results can rely on the default implementation, which does nothing.
+    fn record_result(
+        &mut self,
+        _x: Dimension,
+        _y: Dimension,
+        _result: ShootOk,
+    ) {
+    }
+
+    /// Tells the bot the result of the opponent's shot at (`x`, `y`) of its own battlefield.
+    /// Does nothing by default.
+    fn record_opponent_shot(
+        &mut self,
+        _x: Dimension,
+        _y: Dimension,
+        _result: ShootOk,
+    ) {
+    }
+
+    /// Tells the bot how the game ended. Does nothing by default.
+    fn game_over(
+        &mut self,
+        _verdict: Verdict,
+    ) {
+    }
+}
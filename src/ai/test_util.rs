@@ -0,0 +1,29 @@
+//! Helpers shared by the tests of the bots.
+
+use ai::Shooter;
+use ai::View;
+use common::Player::*;
+use game::Game;
+use ::Dimension;
+
+/// Lets `bot` play player 1 of `game` until it's won, while player 2 keeps shooting at the cell
+/// (`x`, `y`) of player 1's battlefield. Returns the cells the bot shot at, in order.
+pub fn play_against_idle<S: Shooter>(
+    game: &mut Game,
+    bot: &mut S,
+    x: Dimension,
+    y: Dimension,
+) -> Vec<(Dimension, Dimension)> {
+    let mut shots = Vec::new();
+    while game.get_winner().is_none() {
+        if game.current_player() == P2 {
+            game.shoot(P1, x, y).unwrap();
+            continue;
+        }
+        let (bot_x, bot_y) = bot.next_shot(&View::of_opponent(game, P1)).unwrap();
+        let result = game.shoot(P2, bot_x, bot_y).unwrap();
+        bot.record_result(bot_x, bot_y, result);
+        shots.push((bot_x, bot_y));
+    }
+    shots
+}
@@ -0,0 +1,148 @@
+use ai::Difficulty;
+use ai::fleet_tracker::FleetTracker;
+use ai::Shooter;
+use ai::View;
+use analysis::probability_map;
+use common::CellStatus;
+use common::ShipTypeContainer;
+use random::Rng;
+use results::ShootOk;
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use ::Dimension;
+
+/// A bot with a difficulty knob. It plays like `ProbabilityBot` but makes deliberate mistakes
+/// as configured by its `Difficulty`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct TunableBot {
+    rng: Rng,
+    difficulty: Difficulty,
+    fleet: FleetTracker,
+    fired: HashSet<(Dimension, Dimension)>,
+}
+
+impl TunableBot {
+    /// Creates a new instance hunting the ship types of `fleet`, e.g. a `PreGame` or a `Game`.
+    /// Decoys and submerged ships are ignored. Bots with the same difficulty and seed make the
+    /// same mistakes.
+    pub fn new<T: ShipTypeContainer>(
+        fleet: &T,
+        difficulty: Difficulty,
+        seed: u64,
+    ) -> Self {
+        TunableBot {
+            rng: Rng::new(seed),
+            difficulty,
+            fleet: FleetTracker::new(fleet),
+            fired: HashSet::new(),
+        }
+    }
+
+    /// Returns how well the bot plays.
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
+    /// Returns the lengths of the ships the bot believes to be still afloat.
+    pub fn remaining_ships(&self) -> &[Dimension] {
+        self.fleet.remaining()
+    }
+}
+
+impl Shooter for TunableBot {
+    fn next_shot(
+        &mut self,
+        view: &View,
+    ) -> Option<(Dimension, Dimension)> {
+        let mut view = view.clone();
+        for &(x, y) in self.fleet.sunk() {
+            view.set(x, y, CellStatus::Miss);
+        }
+        let open: Vec<(Dimension, Dimension)> = view.unknown_cells().into_iter()
+            .filter(|cell| !self.fired.contains(cell))
+            .collect();
+        if open.is_empty() {
+            return None;
+        }
+
+        let target = if self.rng.chance(self.difficulty.random_shots()) {
+            open[self.rng.below(open.len())]
+        } else {
+            if self.rng.chance(self.difficulty.forgotten_hits()) {
+                for &(x, y) in self.fleet.hits() {
+                    view.set(x, y, CellStatus::Empty);
+                }
+            }
+            let mut ships = self.fleet.remaining().to_vec();
+            ships.sort_by_key(|&length| Reverse(length));
+            if let Some(lookahead) = self.difficulty.lookahead() {
+                ships.truncate(lookahead);
+            }
+            let candidates = probability_map(&view, &ships)
+                .best_cells(|x, y| view.is_unknown(x, y) && !self.fired.contains(&(x, y)));
+            candidates[self.rng.below(candidates.len())]
+        };
+        self.fired.insert(target);
+        Some(target)
+    }
+
+    fn record_result(
+        &mut self,
+        x: Dimension,
+        y: Dimension,
+        result: ShootOk,
+    ) {
+        if result == ShootOk::Armored {
+            self.fired.remove(&(x, y));
+        }
+        self.fleet.record_result(x, y, result);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ai::Difficulty;
+    use ai::test_util::play_against_idle;
+    use common::Orientation::*;
+    use common::Player::*;
+    use pregame::PreGame;
+    use super::TunableBot;
+
+    fn build_test_pregame() -> PreGame {
+        let mut pregame = PreGame::new(8, 8).unwrap();
+        let battleship = pregame.add_ship_type("Battleship", 4).unwrap();
+        let cruiser = pregame.add_ship_type("Cruiser", 3).unwrap();
+        let corvette = pregame.add_ship_type("Corvette", 2).unwrap();
+        for &player in &[P1, P2] {
+            pregame.place_ship(player, &battleship, 1, 6, Horizontal).unwrap();
+            pregame.place_ship(player, &cruiser, 6, 1, Vertical).unwrap();
+            pregame.place_ship(player, &corvette, 2, 2, Vertical).unwrap();
+        }
+
+        pregame
+    }
+
+    fn play(difficulty: Difficulty, seed: u64) -> Vec<(usize, usize)> {
+        let pregame = build_test_pregame();
+        let mut bot = TunableBot::new(&pregame, difficulty, seed);
+        let mut game = pregame.start().unwrap();
+        play_against_idle(&mut game, &mut bot, 0, 0)
+    }
+
+    #[test]
+    fn should_be_reproducible() {
+        assert_eq!(play(Difficulty::easy(), 4), play(Difficulty::easy(), 4));
+        assert_ne!(play(Difficulty::easy(), 4), play(Difficulty::easy(), 5));
+    }
+
+    #[test]
+    fn should_play_worse_when_easier() {
+        let shots = |difficulty| (0..10).map(|seed| play(difficulty, seed).len()).sum::<usize>();
+        let easy = shots(Difficulty::easy());
+        let flawless = shots(Difficulty::default());
+
+        assert!(shots(Difficulty::medium()) < easy);
+        assert!(flawless < easy);
+        assert!(flawless <= shots(Difficulty::hard()) + 10);
+    }
+}
@@ -0,0 +1,123 @@
+use common::CellStatus;
+use common::Dimensional;
+use common::Player;
+use game::Game;
+use ::Dimension;
+
+/// A player's fog-of-war view of their opponent's battlefield, i.e. what
+/// `Game::get_opponent_cell` exposes.
+#[derive(Clone, PartialEq, Debug)]
+pub struct View {
+    cells: Vec<Vec<CellStatus>>,
+}
+
+impl View {
+    /// Creates a view of a battlefield nothing is known about yet.
+    pub fn new(
+        width: Dimension,
+        height: Dimension,
+    ) -> Self {
+        View {
+            cells: vec!(vec!(CellStatus::Empty; width); height),
+        }
+    }
+
+    /// Creates the view `player` has of their opponent's battlefield, i.e. what
+    /// `Game::get_opponent_cell` shows them.
+    pub fn of_opponent(
+        game: &Game,
+        player: Player,
+    ) -> Self {
+        let opponent = player.next();
+        View {
+            cells: (0..game.height())
+                .map(|y| (0..game.width()).map(|x| game.get_opponent_cell(opponent, x, y)).collect())
+                .collect(),
+        }
+    }
+
+    /// Gets the status of the cell (`x`, `y`).
+    ///
+    /// # Panics
+    /// Panics if the x and/or y coordinate is out of bounds.
+    pub fn get(
+        &self,
+        x: Dimension,
+        y: Dimension,
+    ) -> CellStatus {
+        self.cells[y][x]
+    }
+
+    /// Sets the status of the cell (`x`, `y`), e.g. after shooting at it.
+    ///
+    /// # Panics
+    /// Panics if the x and/or y coordinate is out of bounds.
+    pub fn set(
+        &mut self,
+        x: Dimension,
+        y: Dimension,
+        status: CellStatus,
+    ) {
+        self.cells[y][x] = status;
+    }
+
+    /// Returns whether the cell (`x`, `y`) is worth a shot, i.e. whether it hasn't been shot at
+    /// and isn't known to be clear, or holds an armored ship segment that hasn't been damaged
+    /// yet.
+    pub fn is_unknown(
+        &self,
+        x: Dimension,
+        y: Dimension,
+    ) -> bool {
+        matches!(self.get(x, y), CellStatus::Empty | CellStatus::Contact | CellStatus::Armored)
+    }
+
+    /// Returns the coordinates of all cells worth a shot, row by row.
+    pub fn unknown_cells(&self) -> Vec<(Dimension, Dimension)> {
+        (0..self.height())
+            .flat_map(|y| (0..self.width()).map(move |x| (x, y)))
+            .filter(|&(x, y)| self.is_unknown(x, y))
+            .collect()
+    }
+}
+
+impl Dimensional for View {
+    fn width(&self) -> Dimension {
+        self.cells.first().unwrap().len()
+    }
+
+    fn height(&self) -> Dimension {
+        self.cells.len()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::CellStatus;
+    use common::Orientation::*;
+    use common::Player::*;
+    use pregame::PreGame;
+    use super::View;
+
+    #[test]
+    fn should_mirror_the_opponent_cells() {
+        let mut pregame = PreGame::new(3, 3).unwrap();
+        let corvette = pregame.add_ship_type("Corvette", 2).unwrap();
+        pregame.place_ship(P1, &corvette, 0, 0, Horizontal).unwrap();
+        pregame.place_ship(P2, &corvette, 0, 0, Horizontal).unwrap();
+        let mut game = pregame.start().unwrap();
+        game.shoot(P2, 0, 0).unwrap();
+        game.shoot(P2, 2, 2).unwrap();
+
+        let view = View::of_opponent(&game, P1);
+        assert_eq!(CellStatus::Hit, view.get(0, 0));
+        assert_eq!(CellStatus::Miss, view.get(2, 2));
+        assert_eq!(CellStatus::Empty, view.get(1, 0));
+        assert_eq!(7, view.unknown_cells().len());
+        assert_eq!(View::new(3, 3), View::of_opponent(&game, P2));
+
+        game.shoot(P1, 2, 2).unwrap();
+        game.shoot(P2, 1, 0).unwrap();
+        assert_eq!(CellStatus::Hit, View::of_opponent(&game, P1).get(0, 0));
+    }
+}
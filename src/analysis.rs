@@ -0,0 +1,402 @@
+//! Statistics about a player's view of their opponent's battlefield, e.g. to decide where to
+//! shoot next.
+
+use ai::View;
+use common::CellStatus;
+use common::Dimensional;
+use results::Hint;
+use results::HintReason;
+use super::Dimension;
+
+/// How much more likely a placement becomes per unresolved hit it covers. Unresolved hits
+/// belong to a ship that's still afloat, so placements through them are far more likely than
+/// placements in the open.
+const HIT_WEIGHT: f64 = 50.0;
+
+/// For every cell of a battlefield, the likelihood of a ship segment being there.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ProbabilityMap {
+    cells: Vec<Vec<f64>>,
+}
+
+impl ProbabilityMap {
+    /// Returns the likelihood of a ship segment in the cell (`x`, `y`), between 0 and 1. Cells
+    /// that have already been shot at are 0.
+    ///
+    /// # Panics
+    /// Panics if the x and/or y coordinate is out of bounds.
+    pub fn get(
+        &self,
+        x: Dimension,
+        y: Dimension,
+    ) -> f64 {
+        self.cells[y][x]
+    }
+
+    /// Returns all cells sharing the highest likelihood, row by row, considering only the
+    /// cells `is_candidate` accepts.
+    pub fn best_cells<F>(
+        &self,
+        is_candidate: F,
+    ) -> Vec<(Dimension, Dimension)>
+        where F: Fn(Dimension, Dimension) -> bool
+    {
+        self.best_weighted_cells(|_, _| 1.0, is_candidate)
+    }
+
+    /// Like `best_cells`, but each cell's likelihood is multiplied by `weight` first, e.g. to
+    /// favour cells where the opponent used to put their ships.
+    pub fn best_weighted_cells<W, F>(
+        &self,
+        weight: W,
+        is_candidate: F,
+    ) -> Vec<(Dimension, Dimension)>
+        where W: Fn(Dimension, Dimension) -> f64,
+              F: Fn(Dimension, Dimension) -> bool
+    {
+        let score = |x, y| self.get(x, y) * weight(x, y);
+        let candidates: Vec<(Dimension, Dimension)> = (0..self.height())
+            .flat_map(|y| (0..self.width()).map(move |x| (x, y)))
+            .filter(|&(x, y)| is_candidate(x, y))
+            .collect();
+        let best = candidates.iter()
+            .map(|&(x, y)| score(x, y))
+            .fold(0.0, f64::max);
+        candidates.into_iter()
+            .filter(|&(x, y)| score(x, y) >= best)
+            .collect()
+    }
+}
+
+impl Dimensional for ProbabilityMap {
+    fn width(&self) -> Dimension {
+        self.cells.first().unwrap().len()
+    }
+
+    fn height(&self) -> Dimension {
+        self.cells.len()
+    }
+}
+
+/// Computes the likelihood of a ship segment in every unknown cell of `view`, see
+/// `View::is_unknown`, given the lengths of the ships still afloat.
+///
+/// Every legal placement of every remaining ship is counted: horizontally or vertically, on
+/// unknown cells and unresolved hits only. Placements through unresolved hits are weighted up,
+/// as the hits must belong to a remaining ship. Ships are considered independently of each
+/// other, so placements of different ships may overlap. Cells of sunk ships should be marked as
+/// `Miss`, as no other ship can be there.
+///
+/// # Examples
+/// ```
+/// # use lib_battleship::ai::View;
+/// # use lib_battleship::analysis::probability_map;
+/// # use lib_battleship::common::CellStatus;
+/// #
+/// let mut view = View::new(5, 5);
+/// view.set(2, 2, CellStatus::Hit);
+///
+/// let map = probability_map(&view, &[3, 2]);
+/// assert!(map.get(2, 1) > map.get(0, 0));
+/// assert_eq!(0.0, map.get(2, 2));
+/// ```
+pub fn probability_map(
+    view: &View,
+    remaining_fleet: &[Dimension],
+) -> ProbabilityMap {
+    let (width, height) = (view.width(), view.height());
+    let mut misses = vec!(vec!(1.0; width); height);
+
+    for &length in remaining_fleet {
+        let mut weights = vec!(vec!(0.0; width); height);
+        let mut total = 0.0;
+        for (coords, weight) in placements(view, length) {
+            total += weight;
+            for (x, y) in coords {
+                weights[y][x] += weight;
+            }
+        }
+        if total == 0.0 {
+            continue;
+        }
+        for y in 0..height {
+            for x in 0..width {
+                misses[y][x] *= 1.0 - weights[y][x] / total;
+            }
+        }
+    }
+
+    ProbabilityMap {
+        cells: (0..height)
+            .map(|y| (0..width)
+                .map(|x| if view.is_unknown(x, y) { 1.0 - misses[y][x] } else { 0.0 })
+                .collect())
+            .collect(),
+    }
+}
+
+/// Suggests the next cell to shoot at, given `view` and the lengths of all ships of the fleet,
+/// without knowing which ones were destroyed.
+///
+/// Groups of adjacent hits without unknown neighbours or armored cells are considered destroyed,
+/// and ships of their size are no longer expected. Armored cells whose armor held come first,
+/// then the cells next to the remaining hits, those extending a line of hits before the others;
+/// otherwise the cell with the highest placement density is suggested. Ties go to the first cell,
+/// row by row. Returns `None` if there's no unknown cell left.
+///
+/// Assumes a square, non-wrapping battlefield: neighbours and placements stop at the edges.
+///
+/// # Examples
+/// ```
+/// # use lib_battleship::ai::View;
+/// # use lib_battleship::analysis::suggest_shot;
+/// # use lib_battleship::common::CellStatus;
+/// # use lib_battleship::results::HintReason;
+/// #
+/// let mut view = View::new(5, 5);
+/// view.set(1, 1, CellStatus::Hit);
+/// view.set(2, 1, CellStatus::Hit);
+///
+/// let hint = suggest_shot(&view, &[3, 2]).unwrap();
+/// assert_eq!(HintReason::ExtendsLine, hint.reason());
+/// assert_eq!("extends a line of hits", hint.explanation());
+/// ```
+pub fn suggest_shot(
+    view: &View,
+    fleet: &[Dimension],
+) -> Option<Hint> {
+    let mut view = view.clone();
+    let mut remaining = fleet.to_vec();
+    let mut open_hits = Vec::new();
+    for group in hit_groups(&view) {
+        let is_open = group.iter().any(|&(x, y)| view.get(x, y) == CellStatus::Armored
+            || neighbours(&view, x, y).iter().any(|&(nx, ny)| view.is_unknown(nx, ny)));
+        if is_open {
+            open_hits.extend(group);
+            continue;
+        }
+        if let Some(index) = remaining.iter().position(|&length| length == group.len()) {
+            remaining.remove(index);
+        }
+        for (x, y) in group {
+            view.set(x, y, CellStatus::Miss);
+        }
+    }
+
+    let map = probability_map(&view, &remaining);
+    let is_hit = |x: isize, y: isize| x >= 0 && y >= 0 && (x as Dimension) < view.width()
+        && (y as Dimension) < view.height() && is_unresolved_hit(view.get(x as Dimension, y as Dimension));
+    let mut line_ends = Vec::new();
+    let mut adjacent = Vec::new();
+    for &(x, y) in &open_hits {
+        for &(dx, dy) in &[(1, 0), (-1, 0), (0, 1), (0, -1)] {
+            let (nx, ny) = (x as isize + dx, y as isize + dy);
+            if nx < 0 || ny < 0 || nx as Dimension >= view.width() || ny as Dimension >= view.height()
+                || !view.is_unknown(nx as Dimension, ny as Dimension) {
+                continue;
+            }
+            let cell = (nx as Dimension, ny as Dimension);
+            if is_hit(x as isize - dx, y as isize - dy) {
+                line_ends.push(cell);
+            } else {
+                adjacent.push(cell);
+            }
+        }
+    }
+
+    let armored: Vec<(Dimension, Dimension)> = open_hits.iter().cloned()
+        .filter(|&(x, y)| view.get(x, y) == CellStatus::Armored)
+        .collect();
+    let (candidates, reason) = if !armored.is_empty() {
+        (armored, HintReason::ArmorHeld)
+    } else if !line_ends.is_empty() {
+        (line_ends, HintReason::ExtendsLine)
+    } else if !adjacent.is_empty() {
+        (adjacent, HintReason::AdjacentToHit)
+    } else {
+        (view.unknown_cells(), HintReason::HighestDensity)
+    };
+    let mut best: Option<(Dimension, Dimension)> = None;
+    for (x, y) in candidates {
+        let better = match best {
+            None => true,
+            Some((bx, by)) => map.get(x, y) > map.get(bx, by)
+                || (map.get(x, y) == map.get(bx, by) && (y, x) < (by, bx)),
+        };
+        if better {
+            best = Some((x, y));
+        }
+    }
+    best.map(|(x, y)| Hint::new(x, y, reason))
+}
+
+fn is_unresolved_hit(status: CellStatus) -> bool {
+    matches!(status, CellStatus::Hit | CellStatus::Armored)
+}
+
+fn neighbours(
+    view: &View,
+    x: Dimension,
+    y: Dimension,
+) -> Vec<(Dimension, Dimension)> {
+    let mut neighbours = Vec::new();
+    if x > 0 {
+        neighbours.push((x - 1, y));
+    }
+    if y > 0 {
+        neighbours.push((x, y - 1));
+    }
+    if x + 1 < view.width() {
+        neighbours.push((x + 1, y));
+    }
+    if y + 1 < view.height() {
+        neighbours.push((x, y + 1));
+    }
+    neighbours
+}
+
+/// Returns the groups of adjacent unresolved hits, row by row.
+fn hit_groups(view: &View) -> Vec<Vec<(Dimension, Dimension)>> {
+    let mut seen = vec!(vec!(false; view.width()); view.height());
+    let mut groups = Vec::new();
+    for y in 0..view.height() {
+        for x in 0..view.width() {
+            if seen[y][x] || !is_unresolved_hit(view.get(x, y)) {
+                continue;
+            }
+            seen[y][x] = true;
+            let mut group = Vec::new();
+            let mut pending = vec!((x, y));
+            while let Some((cx, cy)) = pending.pop() {
+                group.push((cx, cy));
+                for (nx, ny) in neighbours(view, cx, cy) {
+                    if !seen[ny][nx] && is_unresolved_hit(view.get(nx, ny)) {
+                        seen[ny][nx] = true;
+                        pending.push((nx, ny));
+                    }
+                }
+            }
+            groups.push(group);
+        }
+    }
+    groups
+}
+
+/// Returns the legal placements of a ship of `length` and their weights.
+fn placements(
+    view: &View,
+    length: Dimension,
+) -> Vec<(Vec<(Dimension, Dimension)>, f64)> {
+    let mut placements = Vec::new();
+    for y in 0..view.height() {
+        for x in 0..view.width() {
+            for &(dx, dy) in &[(1, 0), (0, 1)] {
+                if length == 1 && dy == 1 {
+                    continue;
+                }
+                let coords: Vec<(Dimension, Dimension)> = (0..length)
+                    .map(|n| (x + n * dx, y + n * dy))
+                    .take_while(|&(cx, cy)| cx < view.width() && cy < view.height())
+                    .collect();
+                if coords.len() != length {
+                    continue;
+                }
+                let mut weight = 1.0;
+                for &(cx, cy) in &coords {
+                    match view.get(cx, cy) {
+                        CellStatus::Hit | CellStatus::Armored => weight *= HIT_WEIGHT,
+                        _ if view.is_unknown(cx, cy) => {},
+                        _ => weight = 0.0,
+                    }
+                }
+                if weight > 0.0 {
+                    placements.push((coords, weight));
+                }
+            }
+        }
+    }
+    placements
+}
+
+#[cfg(test)]
+mod test {
+    use ai::View;
+    use common::CellStatus;
+    use results::HintReason;
+    use super::probability_map;
+    use super::suggest_shot;
+
+    #[test]
+    fn should_favour_the_centre_of_an_empty_battlefield() {
+        let map = probability_map(&View::new(5, 5), &[3]);
+
+        assert!(map.get(2, 2) > map.get(1, 1));
+        assert!(map.get(1, 1) > map.get(0, 0));
+        assert_eq!(map.get(0, 4), map.get(4, 0));
+        assert_eq!(vec!((2, 2)), map.best_cells(|_, _| true));
+    }
+
+    #[test]
+    fn should_respect_misses() {
+        let mut view = View::new(3, 3);
+        view.set(1, 0, CellStatus::Miss);
+        view.set(1, 1, CellStatus::Miss);
+        view.set(1, 2, CellStatus::Miss);
+
+        let map = probability_map(&view, &[3]);
+        assert_eq!(0.0, map.get(1, 1));
+        assert_eq!(1.0 / 2.0, map.get(0, 0));
+        assert_eq!(1.0 / 2.0, map.get(2, 1));
+    }
+
+    #[test]
+    fn should_target_cells_next_to_hits() {
+        let mut view = View::new(5, 5);
+        view.set(2, 2, CellStatus::Hit);
+        view.set(2, 1, CellStatus::Miss);
+
+        let map = probability_map(&view, &[2]);
+        let best = map.best_cells(|x, y| view.is_unknown(x, y));
+        assert_eq!(vec!((1, 2), (3, 2), (2, 3)), best);
+        assert!(map.get(0, 0) < 0.1);
+    }
+
+    #[test]
+    fn should_suggest_following_up_open_hits() {
+        let mut view = View::new(5, 5);
+        assert_eq!(HintReason::HighestDensity, suggest_shot(&view, &[3]).unwrap().reason());
+
+        view.set(0, 0, CellStatus::Hit);
+        view.set(1, 0, CellStatus::Hit);
+        view.set(2, 0, CellStatus::Miss);
+        view.set(0, 1, CellStatus::Miss);
+        view.set(1, 1, CellStatus::Miss);
+        view.set(3, 3, CellStatus::Hit);
+        let hint = suggest_shot(&view, &[2, 3]).unwrap();
+        assert_eq!(HintReason::AdjacentToHit, hint.reason());
+        assert!([(3, 2), (2, 3), (4, 3), (3, 4)].contains(&(hint.x(), hint.y())));
+
+        view.set(3, 2, CellStatus::Hit);
+        let hint = suggest_shot(&view, &[2, 3]).unwrap();
+        assert_eq!((3, 1, HintReason::ExtendsLine), (hint.x(), hint.y(), hint.reason()));
+
+        view.set(1, 0, CellStatus::Armored);
+        let hint = suggest_shot(&view, &[2, 3]).unwrap();
+        assert_eq!((1, 0, HintReason::ArmorHeld), (hint.x(), hint.y(), hint.reason()));
+    }
+
+    #[test]
+    fn should_have_no_suggestion_without_unknown_cells() {
+        let mut view = View::new(2, 2);
+        for &(x, y) in &[(0, 0), (1, 0), (0, 1), (1, 1)] {
+            view.set(x, y, CellStatus::Miss);
+        }
+        assert_eq!(None, suggest_shot(&view, &[2]));
+    }
+
+    #[test]
+    fn should_be_empty_without_ships() {
+        let map = probability_map(&View::new(2, 2), &[]);
+        assert_eq!(0.0, map.get(0, 0));
+    }
+}
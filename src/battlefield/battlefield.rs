@@ -1,11 +1,24 @@
 use results::GameError::{self, IllegalDimensions};
 use super::Cell;
 use ::Dimension;
+use ::ShipTypeId;
 use common::Dimensional;
+use common::Grid;
+use common::Layer;
+use common::Orientation;
+use common::Topology;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+/// The position of a ship: the coordinates of its bow and its orientation.
+pub type Position = (Dimension, Dimension, Orientation);
 
 #[derive(Clone, PartialEq, Debug)]
 pub struct Battlefield {
-    cells: Vec<Vec<Cell>>,
+    layers: Vec<Vec<Vec<Cell>>>,
+    ships: HashMap<ShipTypeId, (Layer, Position)>,
+    topology: Topology,
+    grid: Grid,
 }
 
 impl Battlefield {
@@ -17,7 +30,12 @@ impl Battlefield {
             Err(IllegalDimensions)
         } else {
             Ok(Battlefield {
-                cells: Battlefield::init_cells(width, height),
+                layers: Layer::ALL.iter()
+                    .map(|_| Battlefield::init_cells(width, height))
+                    .collect(),
+                ships: HashMap::new(),
+                topology: Topology::Rectangular,
+                grid: Grid::Square,
             })
         }
     }
@@ -37,32 +55,238 @@ impl Battlefield {
         ret
     }
 
+    /// Returns the cell at (`x`, `y`) on the surface.
     pub fn get_cell(
         &self,
         x: Dimension,
         y: Dimension,
     ) -> Option<&Cell> {
-        self.cells.get(y)
+        self.get_layer_cell(Layer::Surface, x, y)
+    }
+
+    pub fn get_layer_cell(
+        &self,
+        layer: Layer,
+        x: Dimension,
+        y: Dimension,
+    ) -> Option<&Cell> {
+        self.layers[layer_index(layer)].get(y)
             .and_then(|row| row.get(x))
     }
 
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    pub fn set_topology(
+        &mut self,
+        topology: Topology,
+    ) {
+        self.topology = topology;
+    }
+
+    pub fn grid(&self) -> Grid {
+        self.grid
+    }
+
+    pub fn set_grid(
+        &mut self,
+        grid: Grid,
+    ) {
+        self.grid = grid;
+    }
+
+    /// Returns the coordinates `dx` columns and `dy` lines away from (`x`, `y`), if they lie
+    /// on the battlefield. On a toroidal battlefield, the coordinates wrap around the edges.
+    pub fn offset(
+        &self,
+        x: Dimension,
+        y: Dimension,
+        dx: isize,
+        dy: isize,
+    ) -> Option<(Dimension, Dimension)> {
+        let (width, height) = (self.width() as isize, self.height() as isize);
+        let (x, y) = (x as isize, y as isize);
+        if x >= width || y >= height {
+            return None;
+        }
+
+        match self.topology {
+            Topology::Rectangular => {
+                let (new_x, new_y) = (x + dx, y + dy);
+                if new_x < 0 || new_y < 0 || new_x >= width || new_y >= height {
+                    None
+                } else {
+                    Some((new_x as Dimension, new_y as Dimension))
+                }
+            },
+            Topology::Toroidal => {
+                let new_x = (x + dx).rem_euclid(width);
+                let new_y = (y + dy).rem_euclid(height);
+                Some((new_x as Dimension, new_y as Dimension))
+            },
+        }
+    }
+
+    /// Returns the coordinates a ship of `length` would occupy at `position`, or `None` if
+    /// the ship would exceed any boundary of the battlefield or, on a toroidal battlefield,
+    /// wrap around onto itself. Also `None` if the grid doesn't support the orientation.
+    pub fn ship_coords(
+        &self,
+        length: Dimension,
+        position: Position,
+    ) -> Option<Vec<(Dimension, Dimension)>> {
+        let (x, y, orientation) = position;
+        if !self.grid.supports(orientation) {
+            return None;
+        }
+        let (dx, dy) = match orientation {
+            Orientation::Horizontal => (1, 0),
+            Orientation::Vertical => (0, 1),
+            Orientation::Diagonal => (1, -1),
+        };
+
+        let coords: Vec<(Dimension, Dimension)> = (0..length as isize)
+            .map(|n| self.offset(x, y, n * dx, n * dy))
+            .collect::<Option<_>>()?;
+        let distinct: HashSet<&(Dimension, Dimension)> = coords.iter().collect();
+        if distinct.len() == coords.len() {
+            Some(coords)
+        } else {
+            None
+        }
+    }
+
+    /// Checks that none of the cells at `coords` on `layer` holds a mine or a ship other than
+    /// `ignored`.
+    pub fn is_free(
+        &self,
+        layer: Layer,
+        coords: &[(Dimension, Dimension)],
+        ignored: Option<ShipTypeId>,
+    ) -> bool {
+        coords.iter()
+            .map(|&(x, y)| self.get_layer_cell(layer, x, y).unwrap())
+            .all(|cell| !cell.is_occupied() || (cell.ship_type_id().is_some() && cell.ship_type_id() == ignored))
+    }
+
+    /// Returns all positions where a ship of `length` fits on `layer` without overlapping a
+    /// mine or another ship.
+    pub fn free_positions(
+        &self,
+        length: Dimension,
+        layer: Layer,
+    ) -> Vec<Position> {
+        let mut positions = Vec::new();
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                for &orientation in Orientation::ALL.iter() {
+                    let position = (x, y, orientation);
+                    // single cells look the same in every orientation
+                    if length == 1 && orientation != Orientation::Horizontal {
+                        continue;
+                    }
+                    if let Some(coords) = self.ship_coords(length, position) {
+                        if self.is_free(layer, &coords, None) {
+                            positions.push(position);
+                        }
+                    }
+                }
+            }
+        }
+        positions
+    }
+
+    /// Puts the ship at `position` on `layer`, occupying the cells at `coords`.
+    pub fn place_ship(
+        &mut self,
+        ship_type_id: ShipTypeId,
+        layer: Layer,
+        position: Position,
+        coords: &[(Dimension, Dimension)],
+    ) {
+        for &(x, y) in coords {
+            self.get_mut_layer_cell(layer, x, y).unwrap().set_ship_type_id(ship_type_id);
+        }
+        self.ships.insert(ship_type_id, (layer, position));
+    }
+
+    /// Takes the ship off the battlefield, leaving its cells empty.
+    pub fn remove_ship(
+        &mut self,
+        ship_type_id: ShipTypeId,
+    ) {
+        if let Some(layer) = self.ship_layer(ship_type_id) {
+            for (x, y) in self.ship_cells(ship_type_id) {
+                self.get_mut_layer_cell(layer, x, y).unwrap().clear_ship_type_id();
+            }
+        }
+        self.ships.remove(&ship_type_id);
+    }
+
+    pub fn ship_position(
+        &self,
+        ship_type_id: ShipTypeId,
+    ) -> Option<Position> {
+        self.ships.get(&ship_type_id).map(|&(_, position)| position)
+    }
+
+    pub fn ship_layer(
+        &self,
+        ship_type_id: ShipTypeId,
+    ) -> Option<Layer> {
+        self.ships.get(&ship_type_id).map(|&(layer, _)| layer)
+    }
+
+    /// Returns the coordinates of all cells occupied by the ship.
+    pub fn ship_cells(
+        &self,
+        ship_type_id: ShipTypeId,
+    ) -> Vec<(Dimension, Dimension)> {
+        let layer = match self.ship_layer(ship_type_id) {
+            Some(layer) => layer,
+            None => return Vec::new(),
+        };
+        (0..self.height())
+            .flat_map(|y| (0..self.width()).map(move |x| (x, y)))
+            .filter(|&(x, y)| self.get_layer_cell(layer, x, y).unwrap().ship_type_id() == Some(ship_type_id))
+            .collect()
+    }
+
+    /// Returns the cell at (`x`, `y`) on the surface.
     pub fn get_mut_cell(
         &mut self,
         x: Dimension,
         y: Dimension,
     ) -> Option<&mut Cell> {
-        self.cells.get_mut(y)
+        self.get_mut_layer_cell(Layer::Surface, x, y)
+    }
+
+    pub fn get_mut_layer_cell(
+        &mut self,
+        layer: Layer,
+        x: Dimension,
+        y: Dimension,
+    ) -> Option<&mut Cell> {
+        self.layers[layer_index(layer)].get_mut(y)
             .and_then(|row| row.get_mut(x))
     }
 }
 
+fn layer_index(layer: Layer) -> usize {
+    match layer {
+        Layer::Surface => 0,
+        Layer::Submerged => 1,
+    }
+}
+
 impl Dimensional for Battlefield {
     fn width(&self) -> Dimension {
-        self.cells.first().unwrap().len()
+        self.layers[0].first().unwrap().len()
     }
 
     fn height(&self) -> Dimension {
-        self.cells.len()
+        self.layers[0].len()
     }
 }
 
@@ -70,6 +294,10 @@ impl Dimensional for Battlefield {
 mod test {
     use super::Battlefield;
     use common::Dimensional;
+    use common::Grid;
+    use common::Layer::*;
+    use common::Orientation::*;
+    use common::Topology;
 
     #[test]
     fn should_give_out_cell_references() {
@@ -79,6 +307,90 @@ mod test {
         assert!(bf.get_cell(0, 2).is_none());
     }
 
+    #[test]
+    fn should_compute_ship_coords() {
+        let bf = Battlefield::new(3, 3).unwrap();
+
+        assert_eq!(Some(vec!((1, 0), (2, 0))), bf.ship_coords(2, (1, 0, Horizontal)));
+        assert_eq!(Some(vec!((0, 1), (0, 2))), bf.ship_coords(2, (0, 1, Vertical)));
+        assert_eq!(None, bf.ship_coords(2, (2, 0, Horizontal)));
+    }
+
+    #[test]
+    fn should_compute_diagonal_ship_coords_on_hex_grids() {
+        let mut bf = Battlefield::new(3, 3).unwrap();
+        assert_eq!(None, bf.ship_coords(2, (0, 2, Diagonal)));
+
+        bf.set_grid(Grid::Hexagonal);
+        assert_eq!(Some(vec!((0, 2), (1, 1), (2, 0))), bf.ship_coords(3, (0, 2, Diagonal)));
+        assert_eq!(None, bf.ship_coords(2, (0, 0, Diagonal)));
+    }
+
+    #[test]
+    fn should_place_and_remove_ships() {
+        let mut bf = Battlefield::new(3, 3).unwrap();
+        let coords = bf.ship_coords(2, (0, 0, Horizontal)).unwrap();
+        bf.place_ship(4, Surface, (0, 0, Horizontal), &coords);
+
+        assert_eq!(Some((0, 0, Horizontal)), bf.ship_position(4));
+        assert_eq!(coords, bf.ship_cells(4));
+        assert!(!bf.is_free(Surface, &[(1, 0), (2, 0)], None));
+        assert!(bf.is_free(Surface, &[(1, 0), (2, 0)], Some(4)));
+
+        bf.remove_ship(4);
+        assert_eq!(None, bf.ship_position(4));
+        assert!(bf.is_free(Surface, &coords, None));
+    }
+
+    #[test]
+    fn should_list_free_positions() {
+        let mut bf = Battlefield::new(2, 2).unwrap();
+        assert_eq!(4, bf.free_positions(2, Surface).len());
+        assert_eq!(4, bf.free_positions(1, Surface).len());
+
+        let coords = bf.ship_coords(2, (0, 0, Horizontal)).unwrap();
+        bf.place_ship(4, Surface, (0, 0, Horizontal), &coords);
+        assert_eq!(vec!((0, 1, Horizontal)), bf.free_positions(2, Surface));
+        assert_eq!(4, bf.free_positions(2, Submerged).len());
+    }
+
+    #[test]
+    fn should_keep_layers_apart() {
+        let mut bf = Battlefield::new(3, 3).unwrap();
+        let coords = bf.ship_coords(2, (0, 0, Horizontal)).unwrap();
+        bf.place_ship(4, Submerged, (0, 0, Horizontal), &coords);
+
+        assert_eq!(Some(Submerged), bf.ship_layer(4));
+        assert_eq!(coords, bf.ship_cells(4));
+        assert!(bf.is_free(Surface, &coords, None));
+        assert!(!bf.is_free(Submerged, &coords, None));
+        assert_eq!(None, bf.get_cell(0, 0).unwrap().ship_type_id());
+        assert_eq!(Some(4), bf.get_layer_cell(Submerged, 0, 0).unwrap().ship_type_id());
+    }
+
+    #[test]
+    fn should_offset_coordinates_within_bounds() {
+        let bf = Battlefield::new(3, 2).unwrap();
+
+        assert_eq!(Some((2, 0)), bf.offset(1, 1, 1, -1));
+        assert_eq!(None, bf.offset(0, 0, -1, 0));
+        assert_eq!(None, bf.offset(2, 1, 1, 0));
+        assert_eq!(None, bf.offset(2, 1, 0, 1));
+        assert_eq!(None, bf.offset(3, 0, -1, 0));
+    }
+
+    #[test]
+    fn should_wrap_coordinates_on_toroidal_battlefields() {
+        let mut bf = Battlefield::new(3, 2).unwrap();
+        bf.set_topology(Topology::Toroidal);
+
+        assert_eq!(Some((2, 1)), bf.offset(0, 0, -1, -1));
+        assert_eq!(Some((1, 0)), bf.offset(2, 1, 5, 3));
+        assert_eq!(None, bf.offset(3, 0, -1, 0));
+        assert_eq!(Some(vec!((2, 0), (0, 0))), bf.ship_coords(2, (2, 0, Horizontal)));
+        assert_eq!(None, bf.ship_coords(3, (0, 0, Vertical)));
+    }
+
     #[test]
     fn should_return_width() {
         let bf = Battlefield::new(2, 3).unwrap();
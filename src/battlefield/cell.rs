@@ -1,9 +1,20 @@
 use ::ShipTypeId;
 
+/// What recon scans revealed about a cell.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Sonar {
+    Clear,
+    Contact,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct Cell {
     ship_type_id: Option<ShipTypeId>,
     shot: bool,
+    hits: usize,
+    hit: bool,
+    sonar: Option<Sonar>,
+    mine: bool,
 }
 
 impl Cell {
@@ -11,6 +22,57 @@ impl Cell {
         Cell {
             ship_type_id: None,
             shot: false,
+            hits: 0,
+            hit: false,
+            sonar: None,
+            mine: false,
+        }
+    }
+
+    /// Whether the ship segment in this cell has been damaged. Unlike `is_shot`, this moves
+    /// with the ship.
+    pub fn is_hit(&self) -> bool {
+        self.hit
+    }
+
+    pub fn set_hit(&mut self) {
+        self.hit = true;
+    }
+
+    /// How often the ship segment in this cell has been hit, including hits absorbed by its
+    /// armor. Like `is_hit`, this moves with the ship.
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    pub fn add_hit(&mut self) {
+        self.hits += 1;
+    }
+
+    pub fn has_mine(&self) -> bool {
+        self.mine
+    }
+
+    pub fn set_mine(&mut self) {
+        self.mine = true;
+    }
+
+    /// Whether the cell holds a ship or a mine.
+    pub fn is_occupied(&self) -> bool {
+        self.ship_type_id.is_some() || self.mine
+    }
+
+    pub fn sonar(&self) -> Option<Sonar> {
+        self.sonar
+    }
+
+    /// Records the result of a scan covering this cell. A cell known to be clear stays clear.
+    pub fn record_scan(
+        &mut self,
+        sonar: Sonar,
+    ) {
+        if self.sonar != Some(Sonar::Clear) {
+            self.sonar = Some(sonar);
         }
     }
 
@@ -32,11 +94,18 @@ impl Cell {
     ) {
         self.ship_type_id = Some(ship_type_id)
     }
+
+    pub fn clear_ship_type_id(&mut self) {
+        self.ship_type_id = None;
+        self.hits = 0;
+        self.hit = false;
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::Cell;
+    use super::Sonar;
 
     #[test]
     fn assert_new_cells_are_empty_and_not_shot() {
@@ -50,11 +119,30 @@ mod tests {
         let mut cell = Cell {
             ship_type_id: None,
             shot: false,
+            hits: 0,
+            hit: false,
+            sonar: None,
+            mine: false,
         };
         assert_eq!(None, cell.ship_type_id());
 
         cell.set_ship_type_id(7);
         assert_eq!(Some(7), cell.ship_type_id());
+
+        cell.clear_ship_type_id();
+        assert_eq!(None, cell.ship_type_id());
+    }
+
+    #[test]
+    fn assert_hits_are_counted_until_cleared() {
+        let mut cell = Cell::new();
+        cell.set_ship_type_id(7);
+        cell.add_hit();
+        cell.add_hit();
+        assert_eq!(2, cell.hits());
+
+        cell.clear_ship_type_id();
+        assert_eq!(0, cell.hits());
     }
 
     #[test]
@@ -66,6 +154,28 @@ mod tests {
         assert!(cell.is_shot());
     }
 
+    #[test]
+    fn assert_mines_occupy_cells() {
+        let mut cell = Cell::new();
+        assert!(!cell.is_occupied());
+
+        cell.set_mine();
+        assert!(cell.has_mine());
+        assert!(cell.is_occupied());
+    }
+
+    #[test]
+    fn assert_clear_scans_are_kept() {
+        let mut cell = Cell::new();
+        assert_eq!(None, cell.sonar());
+
+        cell.record_scan(Sonar::Contact);
+        assert_eq!(Some(Sonar::Contact), cell.sonar());
+        cell.record_scan(Sonar::Clear);
+        cell.record_scan(Sonar::Contact);
+        assert_eq!(Some(Sonar::Clear), cell.sonar());
+    }
+
     #[test]
     fn assert_shoot_sets_shot() {
         let mut cell = Cell::new();
@@ -1,5 +1,7 @@
 pub use self::battlefield::Battlefield;
+pub use self::battlefield::Position;
 pub use self::cell::Cell;
+pub use self::cell::Sonar;
 pub use self::ship_status::ShipStatus;
 
 mod battlefield;
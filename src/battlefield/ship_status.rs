@@ -13,12 +13,16 @@ pub struct ShipStatus {
 }
 
 impl ShipStatus {
+    /// Decoys start without any health, so they never count towards the fleet's health.
     pub fn new(
         ship_types: &Vec<Arc<ShipType>>,
     ) -> Self {
+        let health: Vec<Dimension> = ship_types.iter()
+            .map(|st| if st.is_decoy() { 0 } else { st.length() })
+            .collect();
         ShipStatus {
-            status_p1: ship_types.iter().map(|st| st.length()).collect(),
-            status_p2: ship_types.iter().map(|st| st.length()).collect(),
+            status_p1: health.clone(),
+            status_p2: health,
         }
     }
 
@@ -35,6 +39,18 @@ impl ShipStatus {
         }
     }
 
+    pub fn get_health(
+        &self,
+        player: Player,
+        ship_type_id: ShipTypeId,
+    ) -> Dimension {
+        if player == P1 {
+            self.status_p1[ship_type_id]
+        } else {
+            self.status_p2[ship_type_id]
+        }
+    }
+
     pub fn hit(
         &mut self,
         player: Player,
@@ -67,9 +83,21 @@ mod test {
         assert_eq!(2, status.get_sum_health(P1));
         assert_eq!(1, status.hit(P1, 0));
         assert_eq!(1, status.get_sum_health(P1));
+        assert_eq!(1, status.get_health(P1, 0));
 
         assert_eq!(2, status.get_sum_health(P2));
         assert_eq!(1, status.hit(P2, 0));
         assert_eq!(1, status.get_sum_health(P2));
     }
+
+    #[test]
+    fn should_ignore_decoys() {
+        let types = vec!(
+            Arc::new(ShipType::new(0, "Corvette", 2)),
+            Arc::new(ShipType::new(1, "Decoy", 3).with_decoy(true)),
+        );
+        let status = ShipStatus::new(&types);
+
+        assert_eq!(2, status.get_sum_health(P1));
+    }
 }
@@ -0,0 +1,98 @@
+//! Plays a match between two of the bundled bots and prints the results.
+//!
+//! Usage: `battleship-match <bot> <bot> [games] [seed]`, where a bot is `random`, `hunt-target`,
+//! `probability`, `easy`, `medium` or `hard`, optionally followed by a placement strategy, e.g.
+//! `probability:edge-hugging`.
+
+extern crate lib_battleship;
+
+use lib_battleship::PreGame;
+use lib_battleship::ai::Difficulty;
+use lib_battleship::ai::HuntTargetBot;
+use lib_battleship::ai::PlacementStrategy;
+use lib_battleship::ai::ProbabilityBot;
+use lib_battleship::ai::RandomBot;
+use lib_battleship::ai::Shooter;
+use lib_battleship::ai::TunableBot;
+use lib_battleship::tournament::play_match;
+use lib_battleship::tournament::Contestant;
+use lib_battleship::tournament::Record;
+use std::env;
+use std::process;
+
+const USAGE: &str = "usage: battleship-match <bot> <bot> [games] [seed]
+bots: random, hunt-target, probability, easy, medium, hard
+placements (append with ':'): random, edge-hugging, spread-out, anti-heatmap";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.len() < 2 || args.len() > 4 {
+        fail("expected two bots");
+    }
+    let first = contestant(&args[0]);
+    let second = contestant(&args[1]);
+    let games = args.get(2).map(|arg| number(arg)).unwrap_or(1000) as usize;
+    let seed = args.get(3).map(|arg| number(arg)).unwrap_or(0);
+
+    match play_match(setup, &first, &second, games, seed) {
+        Ok(report) => {
+            println!("{} games, {} draws", report.games(), report.draws());
+            print_record(report.first());
+            print_record(report.second());
+        },
+        Err(err) => fail(&format!("match failed: {:?}", err)),
+    }
+}
+
+/// The classic fleet on a 10x10 battlefield.
+fn setup() -> PreGame {
+    let mut pregame = PreGame::new(10, 10).unwrap();
+    pregame.add_ship_type("Carrier", 5).unwrap();
+    pregame.add_ship_type("Battleship", 4).unwrap();
+    pregame.add_ship_type("Cruiser", 3).unwrap();
+    pregame.add_ship_type("Submarine", 3).unwrap();
+    pregame.add_ship_type("Destroyer", 2).unwrap();
+    pregame
+}
+
+fn contestant(arg: &str) -> Contestant<'static> {
+    let mut parts = arg.splitn(2, ':');
+    let bot = parts.next().unwrap();
+    let placement = match parts.next().unwrap_or("random") {
+        "random" => PlacementStrategy::Random,
+        "edge-hugging" => PlacementStrategy::EdgeHugging,
+        "spread-out" => PlacementStrategy::SpreadOut,
+        "anti-heatmap" => PlacementStrategy::AntiHeatmap,
+        other => fail(&format!("unknown placement strategy '{}'", other)),
+    };
+    let shooter: fn(&PreGame, u64) -> Box<dyn Shooter> = match bot {
+        "random" => |_, seed| Box::new(RandomBot::new(seed)),
+        "hunt-target" => |pregame, seed| Box::new(HuntTargetBot::new(pregame, seed)),
+        "probability" => |pregame, seed| Box::new(ProbabilityBot::new(pregame, seed)),
+        "easy" => |pregame, seed| Box::new(TunableBot::new(pregame, Difficulty::easy(), seed)),
+        "medium" => |pregame, seed| Box::new(TunableBot::new(pregame, Difficulty::medium(), seed)),
+        "hard" => |pregame, seed| Box::new(TunableBot::new(pregame, Difficulty::hard(), seed)),
+        other => fail(&format!("unknown bot '{}'", other)),
+    };
+    Contestant::new(arg, placement, shooter)
+}
+
+fn number(arg: &str) -> u64 {
+    arg.parse().unwrap_or_else(|_| fail(&format!("'{}' is not a number", arg)))
+}
+
+fn print_record(record: &Record) {
+    let (low, high) = record.win_rate_interval();
+    print!("{}: {} wins ({:.1}%, 95% CI {:.1}-{:.1}%)",
+        record.name(), record.wins(), 100.0 * record.win_rate(), 100.0 * low, 100.0 * high);
+    match (record.mean_shots_to_win(), record.shots_to_win_interval()) {
+        (Some(mean), Some((low, high))) =>
+            println!(", {:.1} shots to win (95% CI {:.1}-{:.1})", mean, low, high),
+        _ => println!(),
+    }
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("{}\n{}", message, USAGE);
+    process::exit(1)
+}
@@ -1,8 +1,16 @@
-/// The (display) states a cell on the battlefield can have.
+/// The (display) states a cell on the battlefield can have. `Clear` and `Contact` mark cells
+/// covered by a recon scan that found no ships or some ships, respectively. `Mine` marks a
+/// naval mine. `Armored` marks an armored ship segment that has been hit, but not damaged yet.
+/// `Decoy` marks a decoy that has been fully hit, if decoys are revealed.
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub enum CellStatus {
     Empty,
     Miss,
     Ship,
     Hit,
+    Clear,
+    Contact,
+    Mine,
+    Armored,
+    Decoy,
 }
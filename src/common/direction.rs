@@ -0,0 +1,25 @@
+/// The directions a ship can move in. `UpRight` and `DownLeft` are only available on hexagonal
+/// battlefields, where they follow the third hex axis, see `Grid`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+    UpRight,
+    DownLeft,
+}
+
+impl Direction {
+    /// Returns the coordinate offset of a single step in this direction.
+    pub fn offset(&self) -> (isize, isize) {
+        match *self {
+            Direction::Up => (0, -1),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+            Direction::UpRight => (1, -1),
+            Direction::DownLeft => (-1, 1),
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use common::Orientation;
+
+/// The possible shapes of a battlefield's cells.
+///
+/// Hexagonal battlefields use axial coordinates: `x` and `y` are the hex grid's q and r axes, so
+/// the battlefield has the shape of a rhombus. `Horizontal` ships extend along the q axis,
+/// `Vertical` ships along the r axis and `Diagonal` ships along the third axis, i.e. towards
+/// higher x and lower y coordinates.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Grid {
+    Square,
+    Hexagonal,
+}
+
+impl Grid {
+    /// Returns whether ships may be placed in `orientation` on this grid.
+    pub fn supports(
+        &self,
+        orientation: Orientation,
+    ) -> bool {
+        *self == Grid::Hexagonal || orientation != Orientation::Diagonal
+    }
+
+    /// Returns the coordinate offsets from a cell to the cells sharing an edge with it.
+    pub fn neighbour_offsets(&self) -> &'static [(isize, isize)] {
+        match *self {
+            Grid::Square => &[(0, -1), (-1, 0), (1, 0), (0, 1)],
+            Grid::Hexagonal => &[(0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1)],
+        }
+    }
+
+    /// Returns the coordinate offsets from a cell to the cells touching it: its eight surrounding
+    /// cells on a square grid, or its six neighbours on a hexagonal grid.
+    pub fn surrounding_offsets(&self) -> &'static [(isize, isize)] {
+        match *self {
+            Grid::Square => &[(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)],
+            Grid::Hexagonal => self.neighbour_offsets(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::Orientation::*;
+    use super::Grid;
+
+    #[test]
+    fn only_hex_grids_support_diagonal_ships() {
+        assert!(Grid::Square.supports(Horizontal));
+        assert!(!Grid::Square.supports(Diagonal));
+        assert!(Grid::Hexagonal.supports(Diagonal));
+    }
+
+    #[test]
+    fn hex_cells_have_six_neighbours() {
+        assert_eq!(4, Grid::Square.neighbour_offsets().len());
+        assert_eq!(6, Grid::Hexagonal.neighbour_offsets().len());
+        assert_eq!(8, Grid::Square.surrounding_offsets().len());
+        assert_eq!(6, Grid::Hexagonal.surrounding_offsets().len());
+    }
+}
@@ -0,0 +1,13 @@
+/// The depths at which ships can be placed.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Layer {
+    /// The water's surface. Plain shots and most weapons only reach this layer.
+    Surface,
+    /// Below the surface, only reachable by depth charges.
+    Submerged,
+}
+
+impl Layer {
+    /// All layers, from top to bottom.
+    pub const ALL: [Layer; 2] = [Layer::Surface, Layer::Submerged];
+}
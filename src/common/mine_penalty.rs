@@ -0,0 +1,8 @@
+/// The penalties for shooting a naval mine.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum MinePenalty {
+    /// One of the shooter's own unhit ship cells, picked at random, is hit.
+    HitOwnShip,
+    /// The shooter loses their next turn.
+    LoseNextTurn,
+}
@@ -2,14 +2,30 @@
 
 pub use self::cell_status::CellStatus;
 pub use self::dimensional::Dimensional;
+pub use self::direction::Direction;
+pub use self::grid::Grid;
+pub use self::layer::Layer;
+pub use self::mine_penalty::MinePenalty;
+pub use self::movement::Movement;
 pub use self::orientation::Orientation;
 pub use self::player::Player;
+pub use self::scan::Scan;
 pub use self::ship_type::ShipType;
 pub use self::ship_type_container::ShipTypeContainer;
+pub use self::topology::Topology;
+pub use self::weapon::Weapon;
 
 mod cell_status;
 mod dimensional;
+mod direction;
+mod grid;
+mod layer;
+mod mine_penalty;
+mod movement;
 mod orientation;
 mod player;
+mod scan;
 mod ship_type;
 mod ship_type_container;
+mod topology;
+mod weapon;
@@ -0,0 +1,13 @@
+use common::Direction;
+use ::Dimension;
+
+/// The ways a ship can be moved during the game.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Movement {
+    /// Moves the ship the given number of cells in the given direction.
+    Shift(Direction, Dimension),
+    /// Rotates the ship around its bow, i.e. the cell it was placed at. On hexagonal
+    /// battlefields, the ship cycles through the `Horizontal`, `Diagonal` and `Vertical`
+    /// orientations.
+    Rotate,
+}
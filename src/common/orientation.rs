@@ -1,6 +1,13 @@
-/// The possible orientations of a ship in the game.
+/// The possible orientations of a ship in the game. `Diagonal` is only available on hexagonal
+/// battlefields, see `Grid`.
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum Orientation {
     Horizontal,
     Vertical,
+    Diagonal,
+}
+
+impl Orientation {
+    /// All orientations, including those not supported by every grid.
+    pub const ALL: [Orientation; 3] = [Orientation::Horizontal, Orientation::Vertical, Orientation::Diagonal];
 }
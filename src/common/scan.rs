@@ -0,0 +1,16 @@
+use ::Dimension;
+
+/// Recon scans revealing information about a battlefield without dealing damage.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Scan {
+    /// Detects whether any ship occupies the `size` x `size` area whose top left cell is (`x`, `y`).
+    Area {
+        x: Dimension,
+        y: Dimension,
+        size: Dimension,
+    },
+    /// Counts the ship cells in the line `y`.
+    Row(Dimension),
+    /// Counts the ship cells in the column `x`.
+    Column(Dimension),
+}
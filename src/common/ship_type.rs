@@ -1,5 +1,6 @@
 use ::Dimension;
 use ::ShipTypeId;
+use common::Layer;
 
 /// Represents a ship type in the game.
 #[derive(PartialEq, Debug)]
@@ -7,6 +8,10 @@ pub struct ShipType {
     id: ShipTypeId,
     name: &'static str,
     length: Dimension,
+    layer: Layer,
+    armor: usize,
+    decoy: bool,
+    firepower: usize,
 }
 
 impl ShipType {
@@ -23,9 +28,60 @@ impl ShipType {
             id: id,
             name: name,
             length: length,
+            layer: Layer::Surface,
+            armor: 1,
+            decoy: false,
+            firepower: 1,
         }
     }
 
+    /// Moves the ship type to another layer. Ship types live on the surface by default.
+    pub fn with_layer(
+        mut self,
+        layer: Layer,
+    ) -> Self {
+        self.layer = layer;
+        self
+    }
+
+    /// Armors the ship type, so that each segment must be hit `armor` times before it's
+    /// damaged. Ship types have an armor of 1 by default.
+    pub fn with_armor(
+        mut self,
+        armor: usize,
+    ) -> Self {
+        self.armor = armor;
+        self
+    }
+
+    /// Turns the ship type into a decoy. Decoys report hits like real ships, but they don't
+    /// count towards the fleet's health, so they can't be destroyed or decide the game.
+    pub fn with_decoy(
+        mut self,
+        decoy: bool,
+    ) -> Self {
+        self.decoy = decoy;
+        self
+    }
+
+    /// Sets the number of shots ships of this type contribute to each turn while they're afloat,
+    /// see `PreGame::set_firepower`. Ship types have a firepower of 1 by default.
+    pub fn with_firepower(
+        mut self,
+        firepower: usize,
+    ) -> Self {
+        self.firepower = firepower;
+        self
+    }
+
+    pub(crate) fn with_id(
+        mut self,
+        id: ShipTypeId,
+    ) -> Self {
+        self.id = id;
+        self
+    }
+
     /// Returns the id.
     pub fn id(&self) -> ShipTypeId {
         self.id
@@ -40,11 +96,32 @@ impl ShipType {
     pub fn length(&self) -> Dimension {
         self.length
     }
+
+    /// Returns the layer ships of this type are placed on.
+    pub fn layer(&self) -> Layer {
+        self.layer
+    }
+
+    /// Returns the number of hits each segment takes before it's damaged.
+    pub fn armor(&self) -> usize {
+        self.armor
+    }
+
+    /// Returns whether ships of this type are decoys.
+    pub fn is_decoy(&self) -> bool {
+        self.decoy
+    }
+
+    /// Returns the number of shots ships of this type contribute to each turn.
+    pub fn firepower(&self) -> usize {
+        self.firepower
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::ShipType;
+    use common::Layer;
 
     #[test]
     fn constructor_should_work() {
@@ -52,5 +129,33 @@ mod test {
         assert_eq!(7, typ.id());
         assert_eq!("foo", typ.name());
         assert_eq!(5, typ.length());
+        assert_eq!(Layer::Surface, typ.layer());
+        assert_eq!(1, typ.armor());
+        assert!(!typ.is_decoy());
+        assert_eq!(1, typ.firepower());
+    }
+
+    #[test]
+    fn should_change_layer() {
+        let typ = ShipType::new(7, "foo", 5).with_layer(Layer::Submerged);
+        assert_eq!(Layer::Submerged, typ.layer());
+    }
+
+    #[test]
+    fn should_change_armor() {
+        let typ = ShipType::new(7, "foo", 5).with_armor(3);
+        assert_eq!(3, typ.armor());
+    }
+
+    #[test]
+    fn should_make_decoys() {
+        let typ = ShipType::new(7, "foo", 5).with_decoy(true);
+        assert!(typ.is_decoy());
+    }
+
+    #[test]
+    fn should_change_firepower() {
+        let typ = ShipType::new(7, "foo", 5).with_firepower(3);
+        assert_eq!(3, typ.firepower());
     }
 }
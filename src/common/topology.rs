@@ -0,0 +1,8 @@
+/// The possible shapes of a battlefield's surface.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Topology {
+    /// A plain rectangle, ships and weapons stop at its edges.
+    Rectangular,
+    /// A torus, i.e. leaving the battlefield at one edge means entering it at the opposite edge.
+    Toroidal,
+}
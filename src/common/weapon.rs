@@ -0,0 +1,30 @@
+use common::Layer;
+
+/// Special weapons that attack several cells at once.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Weapon {
+    /// Hits the targeted cell and the eight cells surrounding it, or the six cells surrounding
+    /// it on a hexagonal battlefield.
+    Bomb,
+    /// Travels along the targeted row, starting at the targeted cell and heading towards higher
+    /// x coordinates, until it hits a ship or a mine or leaves the battlefield.
+    Torpedo,
+    /// Hits the targeted cell and the cells sharing an edge with it: its four orthogonal
+    /// neighbours, or its six neighbours along the three hex axes on a hexagonal battlefield.
+    CrossStrike,
+    /// Hits the targeted cell on the submerged layer.
+    DepthCharge,
+}
+
+impl Weapon {
+    /// All special weapons.
+    pub const ALL: [Weapon; 4] = [Weapon::Bomb, Weapon::Torpedo, Weapon::CrossStrike, Weapon::DepthCharge];
+
+    /// Returns the layer the weapon reaches.
+    pub fn layer(&self) -> Layer {
+        match *self {
+            Weapon::DepthCharge => Layer::Submerged,
+            _ => Layer::Surface,
+        }
+    }
+}
@@ -0,0 +1,333 @@
+//! A reinforcement learning environment in the style of OpenAI Gym: an agent shoots at a fleet
+//! placed by the computer until it's destroyed, one shot per step.
+//!
+//! ```
+//! use lib_battleship::PreGame;
+//! use lib_battleship::ai::PlacementStrategy;
+//! use lib_battleship::environment::{Environment, Rewards};
+//!
+//! let mut env = Environment::new(|| {
+//!     let mut pregame = PreGame::new(4, 4).unwrap();
+//!     pregame.add_ship_type("Corvette", 2).unwrap();
+//!     pregame
+//! }, PlacementStrategy::Random, Rewards::default());
+//!
+//! let mut observation = env.reset(7).unwrap();
+//! loop {
+//!     // a real policy would look at the observation
+//!     let action = env.action_mask().unwrap().iter().position(|&legal| legal).unwrap();
+//!     let (next, _reward, done) = env.step(action).unwrap();
+//!     observation = next;
+//!     if done {
+//!         break;
+//!     }
+//! }
+//! # assert_eq!(env.observation_len(), observation.len());
+//! ```
+
+use ai::PlacementStrategy;
+use ai::fleet_tracker::FleetTracker;
+use ai::View;
+use common::CellStatus;
+use common::Dimensional;
+use common::Player::*;
+use pregame::PreGame;
+use puzzle::Puzzle;
+use results::EnvironmentError;
+use results::EnvironmentError::*;
+use results::ShootOk;
+use super::Dimension;
+
+/// The number of planes of an observation: misses, hits and sunk ships.
+pub const OBSERVATION_PLANES: usize = 3;
+
+/// The rewards for the results of a shot.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Rewards {
+    miss: f64,
+    hit: f64,
+    destroyed: f64,
+    win: f64,
+}
+
+impl Default for Rewards {
+    /// -1 for a miss, 1 for a hit, 2 for destroying a ship and 10 for destroying the fleet.
+    fn default() -> Self {
+        Rewards {
+            miss: -1.0,
+            hit: 1.0,
+            destroyed: 2.0,
+            win: 10.0,
+        }
+    }
+}
+
+impl Rewards {
+    /// Sets the reward for missing, including hitting a mine.
+    pub fn with_miss(
+        mut self,
+        reward: f64,
+    ) -> Self {
+        self.miss = reward;
+        self
+    }
+
+    /// Sets the reward for hitting a ship, including an armored segment that held.
+    pub fn with_hit(
+        mut self,
+        reward: f64,
+    ) -> Self {
+        self.hit = reward;
+        self
+    }
+
+    /// Sets the reward for destroying a ship.
+    pub fn with_destroyed(
+        mut self,
+        reward: f64,
+    ) -> Self {
+        self.destroyed = reward;
+        self
+    }
+
+    /// Sets the reward for destroying the last ship.
+    pub fn with_win(
+        mut self,
+        reward: f64,
+    ) -> Self {
+        self.win = reward;
+        self
+    }
+
+    /// Returns the reward for `result`.
+    pub fn reward(
+        &self,
+        result: ShootOk,
+    ) -> f64 {
+        match result {
+            ShootOk::Miss | ShootOk::Mine => self.miss,
+            ShootOk::Hit | ShootOk::Armored => self.hit,
+            ShootOk::Destroyed => self.destroyed,
+            ShootOk::WinningShot => self.win,
+        }
+    }
+}
+
+/// A gym-style environment around the rules engine. Actions are cell indices, `y * width + x`.
+pub struct Environment<'a> {
+    setup: Box<dyn Fn() -> PreGame + 'a>,
+    placement: PlacementStrategy,
+    rewards: Rewards,
+    width: Dimension,
+    height: Dimension,
+    puzzle: Option<Puzzle>,
+    fleet: Option<FleetTracker>,
+}
+
+impl<'a> Environment<'a> {
+    /// Creates a new instance. `setup` is called for every episode and must add the ship types;
+    /// it may also configure rules and place some of player 2's ships. The fleet of player 2 is
+    /// completed with `placement`, and the agent shoots at it.
+    ///
+    /// # Panics
+    /// Panics if `setup` returns battlefields of different sizes.
+    pub fn new<F>(
+        setup: F,
+        placement: PlacementStrategy,
+        rewards: Rewards,
+    ) -> Self
+        where F: Fn() -> PreGame + 'a
+    {
+        let pregame = setup();
+        Environment {
+            width: pregame.width(),
+            height: pregame.height(),
+            setup: Box::new(setup),
+            placement,
+            rewards,
+            puzzle: None,
+            fleet: None,
+        }
+    }
+
+    /// Returns the number of actions, i.e. the number of cells.
+    pub fn action_count(&self) -> usize {
+        self.width * self.height
+    }
+
+    /// Returns the length of an observation, see `observation`.
+    pub fn observation_len(&self) -> usize {
+        OBSERVATION_PLANES * self.action_count()
+    }
+
+    /// Starts a new episode with a fleet placed according to `seed` and returns the first
+    /// observation.
+    /// # Errors
+    /// * `FleetDoesNotFit` if the placement strategy couldn't place the fleet
+    pub fn reset(
+        &mut self,
+        seed: u64,
+    ) -> Result<Vec<f32>, EnvironmentError> {
+        self.puzzle = None;
+        self.fleet = None;
+        let mut pregame = (self.setup)();
+        assert_eq!((self.width, self.height), (pregame.width(), pregame.height()));
+        let placements = self.placement.place(&pregame, P2, seed).ok_or(FleetDoesNotFit)?;
+        for placement in placements {
+            placement.apply(&mut pregame, P2).map_err(|_| FleetDoesNotFit)?;
+        }
+        pregame.set_seed(seed);
+        let shots = self.action_count();
+        self.fleet = Some(FleetTracker::new(&pregame));
+        self.puzzle = Some(pregame.start_puzzle(shots).map_err(|_| FleetDoesNotFit)?);
+        self.observation()
+    }
+
+    /// Shoots at the cell `action` and returns the new observation, the reward and whether the
+    /// episode is over.
+    /// # Errors
+    /// * `NotReset` if no episode has been started with `reset`
+    /// * `EpisodeOver` if the episode is over
+    /// * `IllegalAction` if `action` is out of range or masked out, see `action_mask`
+    pub fn step(
+        &mut self,
+        action: usize,
+    ) -> Result<(Vec<f32>, f64, bool), EnvironmentError> {
+        if self.is_done()? {
+            return Err(EpisodeOver);
+        }
+        if !self.action_mask()?.get(action).cloned().unwrap_or(false) {
+            return Err(IllegalAction);
+        }
+        let (x, y) = (action % self.width, action / self.width);
+        let result = self.puzzle.as_mut().unwrap().shoot(x, y).map_err(|_| EpisodeOver)?;
+        self.fleet.as_mut().unwrap().record_result(x, y, result);
+        Ok((self.observation()?, self.rewards.reward(result), self.is_done()?))
+    }
+
+    /// Returns whether the episode is over, i.e. whether the fleet is destroyed or the game
+    /// ended otherwise, e.g. by a time control.
+    /// # Errors
+    /// * `NotReset` if no episode has been started with `reset`
+    pub fn is_done(&self) -> Result<bool, EnvironmentError> {
+        Ok(self.puzzle()?.outcome().is_some())
+    }
+
+    /// Returns the number of shots taken in this episode.
+    /// # Errors
+    /// * `NotReset` if no episode has been started with `reset`
+    pub fn shots(&self) -> Result<usize, EnvironmentError> {
+        Ok(self.puzzle()?.shots_fired())
+    }
+
+    /// Returns the agent's view of the battlefield, flattened into `OBSERVATION_PLANES` planes
+    /// of `width * height` values each, row by row: 1 where the agent missed, 1 where it hit an
+    /// afloat ship and 1 where it sank a ship, as far as the shot results tell. Cells that haven't
+    /// been shot at are 0 in every plane.
+    /// # Errors
+    /// * `NotReset` if no episode has been started with `reset`
+    pub fn observation(&self) -> Result<Vec<f32>, EnvironmentError> {
+        let view = self.view()?;
+        let sunk = self.fleet.as_ref().ok_or(NotReset)?.sunk();
+        let cells = self.action_count();
+        let mut observation = vec!(0.0; self.observation_len());
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let plane = match view.get(x, y) {
+                    CellStatus::Miss | CellStatus::Mine | CellStatus::Clear | CellStatus::Decoy => 0,
+                    CellStatus::Hit if sunk.contains(&(x, y)) => 2,
+                    CellStatus::Hit | CellStatus::Armored => 1,
+                    _ => continue,
+                };
+                observation[plane * cells + y * self.width + x] = 1.0;
+            }
+        }
+        Ok(observation)
+    }
+
+    /// Returns for every action whether it's legal, i.e. whether the cell hasn't been shot at.
+    /// # Errors
+    /// * `NotReset` if no episode has been started with `reset`
+    pub fn action_mask(&self) -> Result<Vec<bool>, EnvironmentError> {
+        let view = self.view()?;
+        Ok((0..self.action_count())
+            .map(|action| view.is_unknown(action % self.width, action / self.width))
+            .collect())
+    }
+
+    fn view(&self) -> Result<View, EnvironmentError> {
+        Ok(View::of_opponent(self.puzzle()?.game(), P1))
+    }
+
+    fn puzzle(&self) -> Result<&Puzzle, EnvironmentError> {
+        self.puzzle.as_ref().ok_or(NotReset)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ai::PlacementStrategy::*;
+    use common::Orientation::*;
+    use common::Player::*;
+    use pregame::PreGame;
+    use results::EnvironmentError::*;
+    use super::Environment;
+    use super::Rewards;
+
+    fn setup() -> PreGame {
+        let mut pregame = PreGame::new(3, 3).unwrap();
+        let corvette = pregame.add_ship_type("Corvette", 2).unwrap();
+        pregame.place_ship(P2, &corvette, 0, 0, Horizontal).unwrap();
+
+        pregame
+    }
+
+    #[test]
+    fn should_play_an_episode() {
+        let rewards = Rewards::default().with_miss(-0.5).with_win(5.0);
+        let mut env = Environment::new(setup, Random, rewards);
+        assert_eq!(Err(NotReset), env.step(0));
+
+        let observation = env.reset(1).unwrap();
+        assert_eq!(27, observation.len());
+        assert!(observation.iter().all(|&value| value == 0.0));
+
+        let (observation, reward, done) = env.step(4).unwrap();
+        assert_eq!((-0.5, false), (reward, done));
+        assert_eq!(1.0, observation[4]);
+        assert!(!env.action_mask().unwrap()[4]);
+        assert_eq!(Err(IllegalAction), env.step(4));
+        assert_eq!(Err(IllegalAction), env.step(9));
+
+        let (observation, reward, _) = env.step(0).unwrap();
+        assert_eq!(1.0, reward);
+        assert_eq!(1.0, observation[9]);
+
+        let (observation, reward, done) = env.step(1).unwrap();
+        assert_eq!((5.0, true), (reward, done));
+        assert_eq!(1.0, observation[18]);
+        assert_eq!(1.0, observation[19]);
+        assert_eq!(0.0, observation[9]);
+        assert_eq!(Ok(3), env.shots());
+        assert_eq!(Err(EpisodeOver), env.step(2));
+    }
+
+    #[test]
+    fn should_place_the_fleet_by_seed() {
+        let setup = || {
+            let mut pregame = PreGame::new(4, 4).unwrap();
+            pregame.add_ship_type("Cruiser", 3).unwrap();
+            pregame.add_ship_type("Corvette", 2).unwrap();
+            pregame
+        };
+        let episode = |seed| {
+            let mut env = Environment::new(setup, EdgeHugging, Rewards::default());
+            env.reset(seed).unwrap();
+            (0..16).map(|action| env.step(action).map(|(_, reward, _)| reward).unwrap_or(0.0))
+                .collect::<Vec<f64>>()
+        };
+
+        assert_eq!(episode(3), episode(3));
+        assert_ne!(episode(3), episode(4));
+    }
+}
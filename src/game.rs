@@ -1,18 +1,47 @@
 //! Implementations for a started game of battleship.
 
+use ai::View;
+use analysis::suggest_shot;
 use battlefield::Battlefield;
 use battlefield::ShipStatus;
+use battlefield::Sonar;
 use common::CellStatus;
 use common::Dimensional;
+use common::Grid;
+use common::Layer;
+use common::MinePenalty;
+use common::Movement;
+use common::Orientation;
 use common::Player::{self, P1, P2};
+use common::Scan;
 use common::ShipType;
 use common::ShipTypeContainer;
+use common::Topology;
+use common::Weapon;
+use results::CommitError;
+use results::DrawError;
+use results::EndReason;
+use results::GameOutcome;
+use results::Hint;
+use results::Impact;
+use results::MoveError;
+use results::ResignError;
+use results::ResolveError;
+use results::Round;
+use results::ScanError;
+use results::ScanOk;
 use results::ShootError;
 use results::ShootError::*;
 use results::ShootOk;
 use results::ShootOk::*;
+use random::Rng;
+use rules::Rules;
 use super::Dimension;
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
+use time::Timeout;
+use time::TurnClock;
 
 /// Struct representing a running game of battleship.
 #[derive(PartialEq, Debug)]
@@ -21,6 +50,24 @@ pub struct Game {
     battlefields: Vec<Battlefield>,
     current_player: Player,
     ship_status: ShipStatus,
+    turn_clock: Option<TurnClock>,
+    shot_limit: Option<usize>,
+    shots_fired: [usize; 2],
+    weapon_uses: HashMap<(Player, Weapon), usize>,
+    recon_charges: [usize; 2],
+    scans: Vec<(Player, Scan, ScanOk)>,
+    mine_penalty: Option<MinePenalty>,
+    skip_turn: [bool; 2],
+    rng: Rng,
+    ship_movement: bool,
+    reveal_decoys: bool,
+    single_player: bool,
+    simultaneous: bool,
+    commits: [Option<(Dimension, Dimension)>; 2],
+    firepower: bool,
+    turn_shots: usize,
+    draw_offer: Option<Player>,
+    outcome: Option<GameOutcome>,
 }
 
 impl Game {
@@ -34,11 +81,48 @@ impl Game {
         ship_types: Vec<Arc<ShipType>>,
         battlefields: Vec<Battlefield>,
     ) -> Self {
+        Game::with_rules(ship_types, battlefields, Rules::default())
+    }
+
+    pub(crate) fn with_rules(
+        ship_types: Vec<Arc<ShipType>>,
+        battlefields: Vec<Battlefield>,
+        rules: Rules,
+    ) -> Self {
+        let mut turn_clock = rules.turn_clock;
+        if let Some(ref mut clock) = turn_clock {
+            clock.start();
+        }
+
+        let mut weapon_uses = HashMap::new();
+        for (weapon, uses) in rules.weapon_uses {
+            weapon_uses.insert((P1, weapon), uses);
+            weapon_uses.insert((P2, weapon), uses);
+        }
+
         Game {
             ship_types: ship_types.clone(),
             battlefields: battlefields,
             current_player: P1,
             ship_status: ShipStatus::new(&ship_types),
+            turn_clock,
+            shot_limit: rules.shot_limit,
+            shots_fired: [0, 0],
+            weapon_uses,
+            recon_charges: [rules.recon_charges, rules.recon_charges],
+            scans: Vec::new(),
+            mine_penalty: rules.mine_penalty,
+            skip_turn: [false, false],
+            rng: rules.seed.map(Rng::new).unwrap_or_else(Rng::from_time),
+            ship_movement: rules.ship_movement,
+            reveal_decoys: rules.reveal_decoys,
+            single_player: rules.single_player,
+            simultaneous: rules.simultaneous,
+            commits: [None, None],
+            firepower: rules.firepower,
+            turn_shots: 0,
+            draw_offer: None,
+            outcome: None,
         }
     }
 
@@ -47,17 +131,21 @@ impl Game {
         self.current_player
     }
 
-    /// Shoot at a player's battlefield.
+    /// Shoot at the surface of a player's battlefield.
     /// # Parameters
     /// * `target_player` The player to be shot at.
     /// * `x` The x coordinate.
     /// * `y` The y coordinate.
     ///
     /// # Errors
-    /// * `NotThisPlayersTurn` if `target_player` is the same as what's returned by `current_player()`.
+    /// * `NotThisPlayersTurn` if `target_player` is the same as what's returned by `current_player()`,
+    ///   or if simultaneous turns are enabled, see `commit_shot`.
     /// * `OutOfBounds` if the given coordinates are outside the boundaries of the battlefield.
     /// * `GameOver` if the game is already finished
     ///
+    /// Any pending draw offer is withdrawn by the shot. Time controls are enforced before the shot
+    /// is taken, so the shooting player may have forfeited their turn or the game already.
+    ///
     /// # Examples
     /// ```
     /// # use lib_battleship::common::Player::{P1, P2};
@@ -75,7 +163,9 @@ impl Game {
     ///     ShootOk::Hit => println!("hit!"),
     ///     ShootOk::Miss => println!("miss!"),
     ///     ShootOk::Destroyed => println!("ship destroyed!"),
-    ///     ShootOk::WinningShot => println!("you won!")
+    ///     ShootOk::WinningShot => println!("you won!"),
+    ///     ShootOk::Mine => println!("you hit a mine!"),
+    ///     ShootOk::Armored => println!("the armor held!")
     /// }
     /// // note that you shouldn't just call `unwrap()` after `shoot()`, don't ignore errors.
     /// ```
@@ -85,297 +175,2042 @@ impl Game {
         x: Dimension,
         y: Dimension,
     ) -> Result<ShootOk, ShootError> {
-        if self.current_player == target_player {
-            return Err(NotThisPlayersTurn);
-        }
-        if let Some(_) = self.get_winner() {
-            return Err(GameOver);
-        }
-        let bf_num = if target_player == P1 {0} else {1};
-        let cell = self.battlefields.get_mut(bf_num).unwrap()
-            .get_mut_cell(x, y)
-            .ok_or(OutOfBounds)?;
-        cell.shoot();
+        self.shoot_layer(target_player, Layer::Surface, x, y)
+    }
 
-        if let Some(ship_type_id) = cell.ship_type_id() {
-            let new_health = self.ship_status.hit(target_player, ship_type_id);
-            let sum_health = self.ship_status.get_sum_health(target_player);
-            if sum_health <= 0 {
-                Ok(WinningShot)
-            } else if new_health <= 0 {
-                Ok(Destroyed)
-            } else {
-                Ok(Hit)
-            }
-        } else {
-            self.current_player = self.current_player.next();
-            Ok(Miss)
+    /// Shoot at a layer of a player's battlefield. Shots at the surface work like `shoot`. Shots
+    /// at the submerged layer use up one of the player's depth charges, see `Weapon::DepthCharge`.
+    /// # Errors
+    /// * `NotThisPlayersTurn` if `target_player` is the same as what's returned by `current_player()`.
+    /// * `OutOfBounds` if the given coordinates are outside the boundaries of the battlefield.
+    /// * `GameOver` if the game is already finished
+    /// * `WeaponUnavailable` if `layer` is `Submerged` and the player has no depth charges left.
+    pub fn shoot_layer(
+        &mut self,
+        target_player: Player,
+        layer: Layer,
+        x: Dimension,
+        y: Dimension,
+    ) -> Result<ShootOk, ShootError> {
+        if layer == Layer::Submerged {
+            let impacts = self.fire(target_player, Weapon::DepthCharge, x, y)?;
+            return Ok(impacts[0].result());
         }
+        self.begin_attack(target_player)?;
+        self.battlefield(target_player).get_cell(x, y).ok_or(OutOfBounds)?;
+
+        let result = self.strike(target_player, layer, x, y);
+        self.finish_attack(is_hit(result));
+        Ok(result)
     }
 
-    /// Gets the status of the cell (`x`, `y`) owned by `player`.
-    /// Does not display missed shots, i.e. misses are considered `Empty` (see `get_opponent_cell`).
+    /// Attack a player's battlefield with a special weapon. Each cell affected by the weapon is
+    /// treated like a single shot on the weapon's layer. The player keeps their turn if any of
+    /// the cells was a hit.
     /// # Parameters
-    /// * `player` determines which battlefield to consider, i.e. the owner of the battlefield.
-    /// * `x` the x coordinate
-    /// * `y` the y coordinate
+    /// * `target_player` The player to be attacked.
+    /// * `weapon` The weapon to use.
+    /// * `x` The x coordinate of the targeted cell.
+    /// * `y` The y coordinate of the targeted cell.
     ///
-    /// # Panics
-    /// Panics if the x and/or y coordinate is out of bounds.
-    pub fn get_cell(
-        &self,
-        player: Player,
+    /// # Errors
+    /// * `NotThisPlayersTurn` if `target_player` is the same as what's returned by `current_player()`.
+    /// * `OutOfBounds` if the targeted cell is outside the boundaries of the battlefield.
+    /// * `GameOver` if the game is already finished
+    /// * `WeaponUnavailable` if the player has no uses of `weapon` left.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lib_battleship::common::Player::{P1, P2};
+    /// # use lib_battleship::common::Weapon;
+    /// # use lib_battleship::PreGame;
+    /// # use lib_battleship::common::Orientation::Horizontal;
+    /// # let mut pregame = PreGame::new(5, 5).unwrap();
+    /// # let corvette = pregame.add_ship_type("Corvette", 2).unwrap();
+    /// # pregame.place_ship(P1, &corvette, 0, 0, Horizontal).unwrap();
+    /// # pregame.place_ship(P2, &corvette, 0, 0, Horizontal).unwrap();
+    /// pregame.set_weapon_uses(Weapon::Bomb, 1);
+    /// let mut game = pregame.start().unwrap();
+    ///
+    /// for impact in game.fire(P2, Weapon::Bomb, 1, 1).unwrap() {
+    ///     println!("({}, {}): {:?}", impact.x(), impact.y(), impact.result());
+    /// }
+    /// ```
+    pub fn fire(
+        &mut self,
+        target_player: Player,
+        weapon: Weapon,
         x: Dimension,
         y: Dimension,
-    ) -> CellStatus {
-        let bf = if player == P1 {
-            self.battlefields.get(0).unwrap()
-        } else {
-            self.battlefields.get(1).unwrap()
-        };
-        let cell = bf.get_cell(x, y).unwrap();
-        let filled = cell.ship_type_id().is_some();
-        let shot = cell.is_shot();
-
-        if filled {
-            if shot {
-                CellStatus::Hit
-            } else {
-                CellStatus::Ship
-            }
-        } else {
-            CellStatus::Empty
+    ) -> Result<Vec<Impact>, ShootError> {
+        self.begin_attack(target_player)?;
+        let key = (self.current_player, weapon);
+        if self.weapon_uses.get(&key).cloned().unwrap_or(0) == 0 {
+            return Err(WeaponUnavailable);
         }
+        let coords = self.weapon_cells(target_player, weapon, x, y)?;
+        *self.weapon_uses.get_mut(&key).unwrap() -= 1;
+
+        let impacts: Vec<Impact> = coords.into_iter()
+            .map(|(x, y)| Impact::new(x, y, self.strike(target_player, weapon.layer(), x, y)))
+            .collect();
+        let hit = impacts.iter().any(|impact| is_hit(impact.result()));
+        self.finish_attack(hit);
+        Ok(impacts)
     }
 
-    /// Gets the status of the *opponent* cell (`x`, `y`) owned by `player`.
-    /// Does not display unhit ship cells, i.e. unhit ship cells are considered `Empty`.
-    /// # Parameters
-    /// * `player` determines which battlefield to consider, i.e. the owner of the battlefield.
-    /// * `x` the x coordinate
-    /// * `y` the y coordinate
-    ///
-    /// # Panics
-    /// Panics if the x and/or y coordinate is out of bounds.
-    pub fn get_opponent_cell(
+    /// Returns how many more times `player` may use `weapon`.
+    pub fn weapon_uses(
         &self,
         player: Player,
+        weapon: Weapon,
+    ) -> usize {
+        self.weapon_uses.get(&(player, weapon)).cloned().unwrap_or(0)
+    }
+
+    fn weapon_cells(
+        &self,
+        target_player: Player,
+        weapon: Weapon,
         x: Dimension,
         y: Dimension,
-    ) -> CellStatus {
-        let bf = if player == P1 {
-            self.battlefields.get(0).unwrap()
-        } else {
-            self.battlefields.get(1).unwrap()
+    ) -> Result<Vec<(Dimension, Dimension)>, ShootError> {
+        let bf = self.battlefield(target_player);
+        bf.get_cell(x, y).ok_or(OutOfBounds)?;
+
+        let offsets: Vec<(isize, isize)> = match weapon {
+            Weapon::Bomb => {
+                let mut offsets = bf.grid().surrounding_offsets().to_vec();
+                offsets.push((0, 0));
+                offsets.sort_by_key(|&(dx, dy)| (dy, dx));
+                offsets
+            },
+            Weapon::CrossStrike => {
+                let mut offsets = bf.grid().neighbour_offsets().to_vec();
+                offsets.push((0, 0));
+                offsets.sort_by_key(|&(dx, dy)| (dy, dx));
+                offsets
+            },
+            Weapon::DepthCharge => vec!((0, 0)),
+            Weapon::Torpedo => {
+                let mut coords = Vec::new();
+                let mut next = Some((x, y));
+                while let Some((cx, cy)) = next {
+                    coords.push((cx, cy));
+                    if bf.get_cell(cx, cy).unwrap().is_occupied() {
+                        break;
+                    }
+                    next = bf.offset(cx, cy, 1, 0)
+                        .filter(|&coords| coords != (x, y));
+                }
+                return Ok(coords);
+            },
         };
-        let cell = bf.get_cell(x, y).unwrap();
-        let filled = cell.ship_type_id().is_some();
-        let shot = cell.is_shot();
 
-        if shot {
-            if filled {
-                CellStatus::Hit
-            } else {
-                CellStatus::Miss
-            }
-        } else {
-            CellStatus::Empty
-        }
+        Ok(offsets.into_iter()
+            .filter_map(|(dx, dy)| bf.offset(x, y, dx, dy))
+            .collect())
     }
 
-    /// Gets the winner of the game, if any.
-    pub fn get_winner(&self) -> Option<Player> {
-        if self.ship_status.get_sum_health(P1) == 0 {
-            Some(P2)
-        } else if self.ship_status.get_sum_health(P2) == 0 {
-            Some(P1)
-        } else {
-            None
+    /// Scan a player's battlefield. Scanning deals no damage but ends the turn. Scans detect ships
+    /// on all layers. Cells covered by an area scan are revealed as `Clear` or `Contact` by
+    /// `get_opponent_cell`. Row and column scans only report the number of ship cells, so their
+    /// cells are revealed as `Clear` if there are none and left unmarked otherwise.
+    /// # Parameters
+    /// * `target_player` The player to be scanned.
+    /// * `scan` The scan to perform.
+    ///
+    /// # Errors
+    /// * `NotThisPlayersTurn` if `target_player` is the same as what's returned by `current_player()`.
+    /// * `OutOfBounds` if the scan exceeds any boundary of the battlefield.
+    /// * `GameOver` if the game is already finished
+    /// * `IllegalScanSize` if an area scan's size is zero.
+    /// * `NoChargesLeft` if the player has used up their recon charges.
+    pub fn scan(
+        &mut self,
+        target_player: Player,
+        scan: Scan,
+    ) -> Result<ScanOk, ScanError> {
+        self.begin_attack(target_player).map_err(|err| match err {
+            NotThisPlayersTurn => ScanError::NotThisPlayersTurn,
+            _ => ScanError::GameOver,
+        })?;
+        let scanner = player_index(self.current_player);
+        if self.recon_charges[scanner] == 0 {
+            return Err(ScanError::NoChargesLeft);
         }
-    }
-}
+        let coords = self.scan_cells(target_player, scan)?;
+        self.recon_charges[scanner] -= 1;
 
-impl Dimensional for Game {
-    fn width(&self) -> Dimension {
-        self.battlefields.first().unwrap().width()
-    }
+        let bf = self.battlefields.get_mut(player_index(target_player)).unwrap();
+        let ship_cells = coords.iter()
+            .flat_map(|&(x, y)| Layer::ALL.iter().map(move |&layer| (layer, x, y)))
+            .filter(|&(layer, x, y)| bf.get_layer_cell(layer, x, y).unwrap().ship_type_id().is_some())
+            .count();
+        let sonar = match scan {
+            _ if ship_cells == 0 => Some(Sonar::Clear),
+            Scan::Area { .. } => Some(Sonar::Contact),
+            Scan::Row(_) | Scan::Column(_) => None,
+        };
+        if let Some(sonar) = sonar {
+            for &(x, y) in &coords {
+                bf.get_mut_cell(x, y).unwrap().record_scan(sonar);
+            }
+        }
 
-    fn height(&self) -> Dimension {
-        self.battlefields.first().unwrap().height()
+        let result = match scan {
+            Scan::Area { .. } if ship_cells == 0 => ScanOk::NoContact,
+            Scan::Area { .. } => ScanOk::Contact,
+            Scan::Row(_) | Scan::Column(_) => ScanOk::ShipCells(ship_cells),
+        };
+        self.scans.push((target_player, scan, result));
+        self.draw_offer = None;
+        self.end_turn();
+        self.apply_shot_limit();
+        Ok(result)
     }
-}
 
-impl ShipTypeContainer for Game {
-    fn ship_types(&self) -> Vec<Arc<ShipType>> {
-        self.ship_types.clone()
+    /// Returns how many more recon scans `player` may perform.
+    pub fn recon_charges(
+        &self,
+        player: Player,
+    ) -> usize {
+        self.recon_charges[player_index(player)]
     }
-}
 
-#[cfg(test)]
-mod test {
-    use battlefield::Battlefield;
-    use common::CellStatus;
-    use common::Dimensional;
-    use common::Orientation::*;
-    use common::Player::*;
-    use common::ShipType;
-    use common::ShipTypeContainer;
-    use pregame::PreGame;
-    use results::ShootError::*;
-    use results::ShootOk::*;
-    use super::Game;
-    use std::sync::Arc;
+    /// Returns the scans performed against `player`'s battlefield and their results, oldest first.
+    pub fn scans(
+        &self,
+        player: Player,
+    ) -> Vec<(Scan, ScanOk)> {
+        self.scans.iter()
+            .filter(|&&(target, _, _)| target == player)
+            .map(|&(_, scan, result)| (scan, result))
+            .collect()
+    }
 
-    #[test]
-    fn should_return_dimensions() {
-        let ship_types = vec!(Arc::new(ShipType::new(0, "Corvette", 2)));
-        let bf1 = Battlefield::new(2, 3).unwrap();
-        let bf2 = bf1.clone();
-        let battlefields = vec!(bf1, bf2);
+    fn scan_cells(
+        &self,
+        target_player: Player,
+        scan: Scan,
+    ) -> Result<Vec<(Dimension, Dimension)>, ScanError> {
+        let (width, height) = (self.width(), self.height());
+        let (x, y, scan_width, scan_height) = match scan {
+            Scan::Area { size: 0, .. } => return Err(ScanError::IllegalScanSize),
+            Scan::Area { size, .. } if size > width || size > height => return Err(ScanError::OutOfBounds),
+            Scan::Area { x, y, size } => (x, y, size, size),
+            Scan::Row(y) => (0, y, width, 1),
+            Scan::Column(x) => (x, 0, 1, height),
+        };
 
-        let game = Game::new(ship_types, battlefields);
-        assert_eq!(2, game.width());
-        assert_eq!(3, game.height());
+        let bf = self.battlefield(target_player);
+        bf.offset(x, y, scan_width as isize - 1, scan_height as isize - 1)
+            .ok_or(ScanError::OutOfBounds)?;
+        Ok((0..scan_height)
+            .flat_map(|dy| (0..scan_width).map(move |dx| (dx, dy)))
+            .filter_map(|(dx, dy)| bf.offset(x, y, dx as isize, dy as isize))
+            .collect())
     }
 
-    #[test]
-    fn should_tell_whos_turn_it_is() {
-        let mut game = build_test_game();
+    /// Move or rotate one of `player`'s undamaged ships instead of shooting. This ends the turn.
+    /// The ship is subject to the same validation as when placing it. Shots recorded against
+    /// cells the ship moves into are kept, i.e. they still appear as misses to the opponent.
+    /// # Parameters
+    /// * `player` The player who owns the ship, i.e. the player whose turn it is.
+    /// * `ship_type` The ship type of the ship to be moved.
+    /// * `movement` How to move the ship.
+    ///
+    /// # Errors
+    /// * `NotThisPlayersTurn` if `player` is not what's returned by `current_player()`.
+    /// * `GameOver` if the game is already finished
+    /// * `MovementNotAllowed` if ship movement hasn't been enabled, see `PreGame::set_ship_movement`.
+    /// * `UnknownShipType` if the ship type isn't part of the game.
+    /// * `ShipDamaged` if the ship has been hit.
+    /// * `IllegalDistance` if the ship is to be shifted by anything but one or two cells.
+    /// * `IllegalDirection` if the ship is to be shifted along a hex axis on a square battlefield.
+    /// * `OutOfBounds` if the ship would exceed any boundary of the battlefield.
+    /// * `CellOccupied` if the ship would occupy a cell holding another ship or a mine.
+    pub fn move_ship(
+        &mut self,
+        player: Player,
+        ship_type: &Arc<ShipType>,
+        movement: Movement,
+    ) -> Result<(), MoveError> {
+        self.check_time();
+        if self.current_player != player || self.simultaneous {
+            return Err(MoveError::NotThisPlayersTurn);
+        }
+        if self.outcome.is_some() {
+            return Err(MoveError::GameOver);
+        }
+        if !self.ship_movement {
+            return Err(MoveError::MovementNotAllowed);
+        }
+        if !self.ship_types.contains(ship_type) {
+            return Err(MoveError::UnknownShipType);
+        }
 
-        assert_eq!(P1, game.current_player());
-        game.shoot(P2, 2, 2).unwrap();
-        assert_eq!(P2, game.current_player());
-    }
+        let ship_type_id = ship_type.id();
+        let bf = self.battlefield(player);
+        let (x, y, orientation) = bf.ship_position(ship_type_id).ok_or(MoveError::UnknownShipType)?;
+        let layer = ship_type.layer();
+        let damaged = bf.ship_cells(ship_type_id).iter()
+            .any(|&(x, y)| bf.get_layer_cell(layer, x, y).unwrap().hits() > 0);
+        if damaged {
+            return Err(MoveError::ShipDamaged);
+        }
 
-    #[test]
-    fn should_respect_order_of_play() {
-        let mut game = build_test_game();
+        let position = match movement {
+            Movement::Shift(_, distance) if distance == 0 || distance > 2 => {
+                return Err(MoveError::IllegalDistance);
+            },
+            Movement::Shift(direction, _)
+                if !bf.grid().neighbour_offsets().contains(&direction.offset()) => {
+                return Err(MoveError::IllegalDirection);
+            },
+            Movement::Shift(direction, distance) => {
+                let (dx, dy) = direction.offset();
+                let distance = distance as isize;
+                let (x, y) = bf.offset(x, y, dx * distance, dy * distance)
+                    .ok_or(MoveError::OutOfBounds)?;
+                (x, y, orientation)
+            },
+            Movement::Rotate => {
+                let orientation = match (bf.grid(), orientation) {
+                    (Grid::Square, Orientation::Horizontal) => Orientation::Vertical,
+                    (Grid::Hexagonal, Orientation::Horizontal) => Orientation::Diagonal,
+                    (_, Orientation::Diagonal) => Orientation::Vertical,
+                    (_, Orientation::Vertical) => Orientation::Horizontal,
+                };
+                (x, y, orientation)
+            },
+        };
+        let coords = bf.ship_coords(ship_type.length(), position).ok_or(MoveError::OutOfBounds)?;
+        if !bf.is_free(layer, &coords, Some(ship_type_id)) {
+            return Err(MoveError::CellOccupied);
+        }
 
-        assert_eq!(Err(NotThisPlayersTurn), game.shoot(P1, 0, 0));
-        game.shoot(P2, 2, 2).unwrap();
-        assert_eq!(Err(NotThisPlayersTurn), game.shoot(P2, 0, 0));
-        game.shoot(P1, 0, 0).unwrap();
+        let bf = self.battlefields.get_mut(player_index(player)).unwrap();
+        bf.remove_ship(ship_type_id);
+        bf.place_ship(ship_type_id, layer, position, &coords);
+        self.draw_offer = None;
+        self.end_turn();
+        self.apply_shot_limit();
+        Ok(())
     }
 
-    #[test]
-    fn a_hit_lets_you_shoot_again() {
-        let mut game = build_test_game();
+    /// Commit `player`'s shot at their opponent's battlefield for this round of simultaneous
+    /// turns, see `PreGame::set_simultaneous_turns`. The shot takes effect once the round is
+    /// resolved, see `resolve_round`.
+    /// # Errors
+    /// * `NotSimultaneous` if simultaneous turns haven't been enabled.
+    /// * `GameOver` if the game is already finished
+    /// * `OutOfBounds` if the given coordinates are outside the boundaries of the battlefield.
+    /// * `AlreadyCommitted` if `player` has already committed a shot this round.
+    /// * `SittingOut` if `player` hit a mine last round or used up their shots.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lib_battleship::common::Player::{P1, P2};
+    /// # use lib_battleship::PreGame;
+    /// # use lib_battleship::common::Orientation::Horizontal;
+    /// # let mut pregame = PreGame::new(3, 3).unwrap();
+    /// # let corvette = pregame.add_ship_type("Corvette", 2).unwrap();
+    /// # pregame.place_ship(P1, &corvette, 0, 0, Horizontal).unwrap();
+    /// # pregame.place_ship(P2, &corvette, 0, 0, Horizontal).unwrap();
+    /// pregame.set_simultaneous_turns(true);
+    /// let mut game = pregame.start().unwrap();
+    ///
+    /// game.commit_shot(P1, 0, 0).unwrap();
+    /// game.commit_shot(P2, 2, 2).unwrap();
+    /// let round = game.resolve_round().unwrap();
+    /// println!("{:?} {:?}", round.result(P1), round.result(P2));
+    /// ```
+    pub fn commit_shot(
+        &mut self,
+        player: Player,
+        x: Dimension,
+        y: Dimension,
+    ) -> Result<(), CommitError> {
+        if !self.simultaneous {
+            return Err(CommitError::NotSimultaneous);
+        }
+        self.check_time();
+        if self.outcome.is_some() {
+            return Err(CommitError::GameOver);
+        }
+        self.battlefield(player.next()).get_cell(x, y).ok_or(CommitError::OutOfBounds)?;
+        if self.is_sitting_out(player) {
+            return Err(CommitError::SittingOut);
+        }
+        let commit = &mut self.commits[player_index(player)];
+        if commit.is_some() {
+            return Err(CommitError::AlreadyCommitted);
+        }
+        *commit = Some((x, y));
+        if let Some(ref mut clock) = self.turn_clock {
+            clock.end_simultaneous_turn(player);
+        }
+        Ok(())
+    }
 
-        // single hit
-        assert_eq!(P1, game.current_player());
-        game.shoot(P2, 0, 0).unwrap();
-        assert_eq!(P1, game.current_player());
-        game.shoot(P2, 2, 2).unwrap();
-        assert_eq!(P2, game.current_player());
+    /// Returns whether `player` has committed their shot for this round.
+    pub fn has_committed(
+        &self,
+        player: Player,
+    ) -> bool {
+        self.commits[player_index(player)].is_some()
+    }
 
-        // destroyed ship
-        game.shoot(P1, 2, 2).unwrap();
-        game.shoot(P2, 1, 0).unwrap();
-        assert_eq!(P1, game.current_player());
+    /// Returns whether the players take simultaneous turns, see `PreGame::set_simultaneous_turns`.
+    pub fn is_simultaneous(&self) -> bool {
+        self.simultaneous
     }
 
-    #[test]
-    fn shooting_out_of_bounds_is_an_error() {
-        let mut game = build_test_game();
+    /// Resolve the current round of simultaneous turns, taking the committed shots at once. If
+    /// both fleets are destroyed in the same round, the game ends in a draw.
+    /// # Errors
+    /// * `NotSimultaneous` if simultaneous turns haven't been enabled.
+    /// * `GameOver` if the game is already finished
+    /// * `NotAllCommitted` if a player who doesn't sit out this round hasn't committed a shot.
+    pub fn resolve_round(&mut self) -> Result<Round, ResolveError> {
+        if !self.simultaneous {
+            return Err(ResolveError::NotSimultaneous);
+        }
+        self.check_time();
+        if self.outcome.is_some() {
+            return Err(ResolveError::GameOver);
+        }
+        if [P1, P2].iter().any(|&player| !self.has_committed(player) && !self.is_sitting_out(player)) {
+            return Err(ResolveError::NotAllCommitted);
+        }
 
-        assert_eq!(Err(OutOfBounds), game.shoot(P2, 3, 0));
-        assert_eq!(Err(OutOfBounds), game.shoot(P2, 0, 3));
-        game.shoot(P2, 0, 0).unwrap();
+        let mut results = [None, None];
+        for &player in &[P1, P2] {
+            let index = player_index(player);
+            match self.commits[index].take() {
+                Some((x, y)) => {
+                    results[index] = Some(self.strike(player.next(), Layer::Surface, x, y));
+                    self.shots_fired[index] += 1;
+                },
+                None => self.skip_turn[index] = false,
+            }
+        }
+        if self.ship_status.get_sum_health(P1) == 0 && self.ship_status.get_sum_health(P2) == 0 {
+            self.end_game(None, EndReason::FleetDestroyed);
+        }
+        if let Some(ref mut clock) = self.turn_clock {
+            clock.start();
+        }
+        self.draw_offer = None;
+        self.apply_shot_limit();
+        Ok(Round::new(results[0], results[1]))
     }
 
-    #[test]
-    fn shooting_at_empty_cells_is_a_miss() {
-        let mut game = build_test_game();
+    /// Returns whether `player` sits out the current round of simultaneous turns, because they hit
+    /// a mine, exceeded the turn limit or used up their shots.
+    pub fn is_sitting_out(
+        &self,
+        player: Player,
+    ) -> bool {
+        let index = player_index(player);
+        self.skip_turn[index] || self.shot_limit.is_some_and(|limit| self.shots_fired[index] >= limit)
+    }
 
-        assert_eq!(Ok(Miss), game.shoot(P2, 1, 1));
-        assert_eq!(Ok(Miss), game.shoot(P1, 1, 1));
+    /// Returns the firepower of `player`'s surviving ships, i.e. the number of shots they may
+    /// take per turn if the firepower rule is enabled, see `PreGame::set_firepower`.
+    pub fn firepower(
+        &self,
+        player: Player,
+    ) -> usize {
+        self.ship_types.iter()
+            .filter(|ship_type| !ship_type.is_decoy() && self.ship_status.get_health(player, ship_type.id()) > 0)
+            .map(|ship_type| ship_type.firepower())
+            .sum()
     }
 
-    #[test]
-    fn shooting_at_filled_cells_is_a_hit() {
-        let mut game = build_test_game();
+    /// Returns how many more shots the current player may take this turn if the firepower rule
+    /// is enabled, or `None` otherwise.
+    pub fn shots_left_in_turn(&self) -> Option<usize> {
+        if self.firepower {
+            Some(self.firepower(self.current_player).saturating_sub(self.turn_shots))
+        } else {
+            None
+        }
+    }
 
-        assert_eq!(Ok(Hit), game.shoot(P2, 0, 0));
+    /// Returns the remaining health of `player`'s fleet, i.e. the number of undamaged segments
+    /// of their real ships.
+    pub(crate) fn fleet_health(
+        &self,
+        player: Player,
+    ) -> Dimension {
+        self.ship_status.get_sum_health(player)
+    }
+
+    fn begin_attack(
+        &mut self,
+        target_player: Player,
+    ) -> Result<(), ShootError> {
+        self.check_time();
+        if self.current_player == target_player || self.simultaneous {
+            return Err(NotThisPlayersTurn);
+        }
+        if self.outcome.is_some() {
+            return Err(GameOver);
+        }
+        Ok(())
+    }
+
+    /// Shoots at a single cell, damaging the ship segment in it unless it's already damaged, or
+    /// triggering the mine in it unless the cell has been shot before.
+    fn strike(
+        &mut self,
+        target_player: Player,
+        layer: Layer,
+        x: Dimension,
+        y: Dimension,
+    ) -> ShootOk {
+        let cell = self.battlefields.get_mut(player_index(target_player)).unwrap()
+            .get_mut_layer_cell(layer, x, y)
+            .unwrap();
+        let already_shot = cell.is_shot();
+        cell.shoot();
+
+        if cell.has_mine() {
+            if !already_shot {
+                self.trigger_mine(target_player.next());
+            }
+            return Mine;
+        }
+
+        match cell.ship_type_id() {
+            None => Miss,
+            Some(_) if cell.is_hit() => Hit,
+            Some(ship_type_id) => {
+                cell.add_hit();
+                if cell.hits() < self.ship_types[ship_type_id].armor() {
+                    return Armored;
+                }
+                cell.set_hit();
+                if self.ship_types[ship_type_id].is_decoy() {
+                    return Hit;
+                }
+                let new_health = self.ship_status.hit(target_player, ship_type_id);
+                let sum_health = self.ship_status.get_sum_health(target_player);
+                if sum_health <= 0 {
+                    self.end_game(Some(target_player.next()), EndReason::FleetDestroyed);
+                    WinningShot
+                } else if new_health <= 0 {
+                    Destroyed
+                } else {
+                    Hit
+                }
+            },
+        }
+    }
+
+    fn trigger_mine(
+        &mut self,
+        shooter: Player,
+    ) {
+        match self.mine_penalty {
+            Some(MinePenalty::HitOwnShip) => {
+                let bf = self.battlefield(shooter);
+                let (width, height) = (bf.width(), bf.height());
+                let candidates: Vec<(Layer, Dimension, Dimension)> = Layer::ALL.iter()
+                    .flat_map(|&layer| (0..height).flat_map(move |y| (0..width).map(move |x| (layer, x, y))))
+                    .filter(|&(layer, x, y)| {
+                        let cell = bf.get_layer_cell(layer, x, y).unwrap();
+                        cell.ship_type_id().is_some() && !cell.is_hit()
+                    })
+                    .collect();
+                if !candidates.is_empty() {
+                    let (layer, x, y) = candidates[self.rng.below(candidates.len())];
+                    self.strike(shooter, layer, x, y);
+                }
+            },
+            Some(MinePenalty::LoseNextTurn) => self.skip_turn[player_index(shooter)] = true,
+            None => {},
+        }
+    }
+
+    fn finish_attack(
+        &mut self,
+        hit: bool,
+    ) {
+        self.draw_offer = None;
+        self.shots_fired[player_index(self.current_player)] += 1;
+        if self.firepower {
+            self.turn_shots += 1;
+            if self.turn_shots >= self.firepower(self.current_player) {
+                self.end_turn();
+            }
+        } else if !hit {
+            self.end_turn();
+        }
+        self.apply_shot_limit();
+    }
+
+    fn battlefield(
+        &self,
+        player: Player,
+    ) -> &Battlefield {
+        self.battlefields.get(player_index(player)).unwrap()
+    }
+
+    fn end_turn(&mut self) {
+        if let Some(ref mut clock) = self.turn_clock {
+            clock.end_turn(self.current_player);
+        }
+        self.next_turn();
+    }
+
+    /// Hands the turn to the next player who may take it, without touching the turn clock. In
+    /// single player games, the turn always stays with the current player.
+    fn next_turn(&mut self) {
+        self.turn_shots = 0;
+        if self.single_player {
+            return;
+        }
+        self.current_player = self.current_player.next();
+
+        let index = player_index(self.current_player);
+        if self.skip_turn[index] {
+            self.skip_turn[index] = false;
+            self.current_player = self.current_player.next();
+        }
+
+        if self.firepower && self.outcome.is_none() && self.firepower(self.current_player) == 0 {
+            self.current_player = self.current_player.next();
+            if self.firepower(self.current_player) == 0 {
+                let winner = self.winner_by_health();
+                self.end_game(winner, EndReason::NoFirepower);
+            }
+        }
+    }
+
+    fn end_game(
+        &mut self,
+        winner: Option<Player>,
+        reason: EndReason,
+    ) {
+        self.outcome = Some(GameOutcome::new(winner, reason));
+    }
+
+    /// Returns the player with more undamaged ship segments left, or `None` on a tie.
+    fn winner_by_health(&self) -> Option<Player> {
+        let health_p1 = self.ship_status.get_sum_health(P1);
+        let health_p2 = self.ship_status.get_sum_health(P2);
+        if health_p1 > health_p2 {
+            Some(P1)
+        } else if health_p2 > health_p1 {
+            Some(P2)
+        } else {
+            None
+        }
+    }
+
+    fn apply_shot_limit(&mut self) {
+        let limit = match self.shot_limit {
+            Some(limit) if self.outcome.is_none() => limit,
+            _ => return,
+        };
+        let exhausted = |player: Player| self.shots_fired[player_index(player)] >= limit;
+
+        if exhausted(P1) && exhausted(P2) {
+            let winner = self.winner_by_health();
+            self.end_game(winner, EndReason::ShotLimit);
+        } else if exhausted(self.current_player) && !self.simultaneous {
+            self.end_turn();
+        }
+    }
+
+    /// Lets `player` resign, making their opponent the winner.
+    ///
+    /// # Errors
+    /// * `GameOver` if the game is already finished
+    pub fn resign(
+        &mut self,
+        player: Player,
+    ) -> Result<(), ResignError> {
+        if self.outcome.is_some() {
+            return Err(ResignError::GameOver);
+        }
+        self.end_game(Some(player.next()), EndReason::Resignation);
+        Ok(())
+    }
+
+    /// Lets `player` offer a draw to their opponent. The offer stands until it's accepted,
+    /// declined or the next shot is taken. If the opponent has already offered a draw, this
+    /// accepts it.
+    ///
+    /// # Errors
+    /// * `GameOver` if the game is already finished
+    pub fn offer_draw(
+        &mut self,
+        player: Player,
+    ) -> Result<(), DrawError> {
+        if self.outcome.is_some() {
+            return Err(DrawError::GameOver);
+        }
+        if self.draw_offer == Some(player.next()) {
+            return self.accept_draw(player);
+        }
+        self.draw_offer = Some(player);
+        Ok(())
+    }
+
+    /// Lets `player` accept the draw offered by their opponent, ending the game.
+    ///
+    /// # Errors
+    /// * `GameOver` if the game is already finished
+    /// * `NoDrawOffered` if the opponent hasn't offered a draw
+    pub fn accept_draw(
+        &mut self,
+        player: Player,
+    ) -> Result<(), DrawError> {
+        if self.outcome.is_some() {
+            return Err(DrawError::GameOver);
+        }
+        if self.draw_offer != Some(player.next()) {
+            return Err(DrawError::NoDrawOffered);
+        }
+        self.end_game(None, EndReason::AgreedDraw);
+        Ok(())
+    }
+
+    /// Lets `player` decline the draw offered by their opponent.
+    ///
+    /// # Errors
+    /// * `GameOver` if the game is already finished
+    /// * `NoDrawOffered` if the opponent hasn't offered a draw
+    pub fn decline_draw(
+        &mut self,
+        player: Player,
+    ) -> Result<(), DrawError> {
+        if self.outcome.is_some() {
+            return Err(DrawError::GameOver);
+        }
+        if self.draw_offer != Some(player.next()) {
+            return Err(DrawError::NoDrawOffered);
+        }
+        self.draw_offer = None;
+        Ok(())
+    }
+
+    /// Returns the player with a pending draw offer, if any.
+    pub fn draw_offer(&self) -> Option<Player> {
+        self.draw_offer
+    }
+
+    /// Enforces the time controls, if any. A player who exceeded the turn limit forfeits their
+    /// turn, a player whose time bank ran out forfeits the game. `shoot` does this
+    /// automatically, call this function to enforce the time controls in between shots.
+    ///
+    /// With simultaneous turns, every round is a turn for both players, which ends for a player
+    /// once they commit their shot, see `commit_shot`. A player who exceeded the turn limit sits
+    /// out the round; if both players' time banks run out, the game is a draw.
+    pub fn check_time(&mut self) {
+        if self.simultaneous {
+            self.check_round_time();
+            return;
+        }
+        while self.outcome.is_none() {
+            let timeout = match self.turn_clock {
+                Some(ref mut clock) => clock.check(self.current_player),
+                None => None,
+            };
+
+            match timeout {
+                Some(Timeout::Turn) => self.next_turn(),
+                Some(Timeout::Game) => {
+                    let winner = self.current_player.next();
+                    self.end_game(Some(winner), EndReason::Timeout);
+                },
+                None => return,
+            }
+        }
+    }
+
+    /// Enforces the time controls for the players who still have to commit a shot this round.
+    fn check_round_time(&mut self) {
+        if self.outcome.is_some() {
+            return;
+        }
+        let mut out_of_time = Vec::new();
+        for &player in &[P1, P2] {
+            if self.has_committed(player) || self.is_sitting_out(player) {
+                continue;
+            }
+            let timeout = match self.turn_clock {
+                Some(ref mut clock) => clock.check_simultaneous(player),
+                None => None,
+            };
+            match timeout {
+                Some(Timeout::Turn) => self.skip_turn[player_index(player)] = true,
+                Some(Timeout::Game) => out_of_time.push(player),
+                None => {},
+            }
+        }
+        match out_of_time.len() {
+            0 => {},
+            1 => self.end_game(Some(out_of_time[0].next()), EndReason::Timeout),
+            _ => self.end_game(None, EndReason::Timeout),
+        }
+    }
+
+    /// Returns the time left in `player`'s time bank, or `None` if the game has no time bank.
+    /// Does not enforce the time controls, see `check_time`.
+    pub fn remaining_time(
+        &self,
+        player: Player,
+    ) -> Option<Duration> {
+        let running = if self.simultaneous {
+            !self.has_committed(player) && !self.is_sitting_out(player)
+        } else {
+            player == self.current_player
+        };
+        let current_player = if running { player } else { player.next() };
+        self.turn_clock.as_ref()
+            .and_then(|clock| clock.remaining(player, current_player))
+    }
+
+    /// Returns the time left in the current turn, or `None` if the game has no turn limit.
+    /// Does not enforce the time controls, see `check_time`.
+    pub fn remaining_turn_time(&self) -> Option<Duration> {
+        self.turn_clock.as_ref()
+            .and_then(|clock| clock.remaining_in_turn())
+    }
+
+    /// Gets the status of the surface cell (`x`, `y`) owned by `player`.
+    /// Does not display missed shots, i.e. misses are considered `Empty` (see `get_opponent_cell`).
+    /// Mines are displayed as `Mine`, whether they have been shot or not.
+    /// # Parameters
+    /// * `player` determines which battlefield to consider, i.e. the owner of the battlefield.
+    /// * `x` the x coordinate
+    /// * `y` the y coordinate
+    ///
+    /// # Panics
+    /// Panics if the x and/or y coordinate is out of bounds.
+    pub fn get_cell(
+        &self,
+        player: Player,
+        x: Dimension,
+        y: Dimension,
+    ) -> CellStatus {
+        self.get_layer_cell(player, Layer::Surface, x, y)
+    }
+
+    /// Like `get_cell`, but for the cell (`x`, `y`) on `layer`.
+    ///
+    /// # Panics
+    /// Panics if the x and/or y coordinate is out of bounds.
+    pub fn get_layer_cell(
+        &self,
+        player: Player,
+        layer: Layer,
+        x: Dimension,
+        y: Dimension,
+    ) -> CellStatus {
+        let cell = self.battlefield(player).get_layer_cell(layer, x, y).unwrap();
+        let filled = cell.ship_type_id().is_some();
+
+        if filled {
+            if cell.is_hit() {
+                CellStatus::Hit
+            } else if cell.hits() > 0 {
+                CellStatus::Armored
+            } else {
+                CellStatus::Ship
+            }
+        } else if cell.has_mine() {
+            CellStatus::Mine
+        } else {
+            CellStatus::Empty
+        }
+    }
+
+    /// Gets the status of the *opponent* surface cell (`x`, `y`) owned by `player`.
+    /// Does not display unhit ship cells, i.e. unhit ship cells are considered `Empty` unless
+    /// they have been scanned, see `scan`. Hit decoys are displayed as `Hit`, unless decoys are
+    /// revealed, see `PreGame::set_decoy_reveal`.
+    /// # Parameters
+    /// * `player` determines which battlefield to consider, i.e. the owner of the battlefield.
+    /// * `x` the x coordinate
+    /// * `y` the y coordinate
+    ///
+    /// # Panics
+    /// Panics if the x and/or y coordinate is out of bounds.
+    pub fn get_opponent_cell(
+        &self,
+        player: Player,
+        x: Dimension,
+        y: Dimension,
+    ) -> CellStatus {
+        self.get_opponent_layer_cell(player, Layer::Surface, x, y)
+    }
+
+    /// Like `get_opponent_cell`, but for the cell (`x`, `y`) on `layer`. Scans only mark the
+    /// surface.
+    ///
+    /// # Panics
+    /// Panics if the x and/or y coordinate is out of bounds.
+    pub fn get_opponent_layer_cell(
+        &self,
+        player: Player,
+        layer: Layer,
+        x: Dimension,
+        y: Dimension,
+    ) -> CellStatus {
+        let bf = self.battlefield(player);
+        let cell = bf.get_layer_cell(layer, x, y).unwrap();
+        if cell.is_hit() {
+            match cell.ship_type_id() {
+                Some(ship_type_id) if self.reveal_decoys
+                    && self.ship_types[ship_type_id].is_decoy()
+                    && bf.ship_cells(ship_type_id).iter()
+                        .all(|&(x, y)| bf.get_layer_cell(layer, x, y).unwrap().is_hit()) => CellStatus::Decoy,
+                _ => CellStatus::Hit,
+            }
+        } else if cell.hits() > 0 {
+            CellStatus::Armored
+        } else if cell.is_shot() {
+            if cell.has_mine() {
+                CellStatus::Mine
+            } else {
+                CellStatus::Miss
+            }
+        } else {
+            match cell.sonar() {
+                Some(Sonar::Clear) => CellStatus::Clear,
+                Some(Sonar::Contact) => CellStatus::Contact,
+                None => CellStatus::Empty,
+            }
+        }
+    }
+
+    /// Suggests where `player` should shoot next, with a short explanation, see
+    /// `analysis::suggest_shot`. Only uses what `get_opponent_cell` shows `player` and the
+    /// ship types of the fleet, never where the opponent's ships are. Only surface ships are
+    /// considered, as shots can't reach the others. Returns `None` if the game is over, there's no
+    /// cell worth a shot, or the battlefield is toroidal or hexagonal, as the analysis only knows
+    /// square, non-wrapping battlefields.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lib_battleship::common::Player::{P1, P2};
+    /// # use lib_battleship::PreGame;
+    /// # use lib_battleship::common::Orientation::Horizontal;
+    /// # let mut pregame = PreGame::new(5, 5).unwrap();
+    /// # let corvette = pregame.add_ship_type("Corvette", 2).unwrap();
+    /// # pregame.place_ship(P1, &corvette, 0, 0, Horizontal).unwrap();
+    /// # pregame.place_ship(P2, &corvette, 0, 0, Horizontal).unwrap();
+    /// let game = pregame.start().unwrap();
+    ///
+    /// let hint = game.suggest_shot(P1).unwrap();
+    /// println!("try ({}, {}): {}", hint.x(), hint.y(), hint.explanation());
+    /// ```
+    pub fn suggest_shot(
+        &self,
+        player: Player,
+    ) -> Option<Hint> {
+        let opponent = player.next();
+        let battlefield = self.battlefield(opponent);
+        if self.outcome().is_some() || battlefield.topology() != Topology::Rectangular
+            || battlefield.grid() != Grid::Square {
+            return None;
+        }
+        let mut view = View::new(self.width(), self.height());
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                view.set(x, y, self.get_opponent_cell(opponent, x, y));
+            }
+        }
+        let fleet: Vec<Dimension> = self.ship_types.iter()
+            .filter(|ship_type| !ship_type.is_decoy() && ship_type.layer() == Layer::Surface)
+            .map(|ship_type| ship_type.length())
+            .collect();
+        suggest_shot(&view, &fleet)
+    }
+
+    /// Gets the winner of the game, if any. A game can also end in a draw, see `outcome`.
+    pub fn get_winner(&self) -> Option<Player> {
+        self.outcome.and_then(|outcome| outcome.winner())
+    }
+
+    /// Gets the outcome of the game, or `None` while the game is still running.
+    pub fn outcome(&self) -> Option<GameOutcome> {
+        self.outcome
+    }
+}
+
+fn is_hit(result: ShootOk) -> bool {
+    match result {
+        Hit | Destroyed | WinningShot | Armored => true,
+        Miss | Mine => false,
+    }
+}
+
+fn player_index(player: Player) -> usize {
+    if player == P1 {0} else {1}
+}
+
+impl Dimensional for Game {
+    fn width(&self) -> Dimension {
+        self.battlefields.first().unwrap().width()
+    }
+
+    fn height(&self) -> Dimension {
+        self.battlefields.first().unwrap().height()
+    }
+}
+
+impl ShipTypeContainer for Game {
+    fn ship_types(&self) -> Vec<Arc<ShipType>> {
+        self.ship_types.clone()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use battlefield::Battlefield;
+    use common::CellStatus;
+    use common::Dimensional;
+    use common::Direction::*;
+    use common::Grid;
+    use common::Layer::*;
+    use common::MinePenalty;
+    use common::Movement::*;
+    use common::Orientation::*;
+    use common::Player::*;
+    use common::Scan;
+    use common::ShipType;
+    use common::ShipTypeContainer;
+    use common::Topology;
+    use common::Weapon;
+    use pregame::PreGame;
+    use results::CommitError;
+    use results::DrawError;
+    use results::EndReason;
+    use results::GameOutcome;
+    use results::HintReason;
+    use results::Impact;
+    use results::MoveError;
+    use results::ResignError;
+    use results::ResolveError;
+    use results::ScanError;
+    use results::ScanOk;
+    use results::ShootError::*;
+    use results::ShootOk::*;
+    use super::Game;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use time::ManualClock;
+    use time::TimeControl;
+
+    #[test]
+    fn should_return_dimensions() {
+        let ship_types = vec!(Arc::new(ShipType::new(0, "Corvette", 2)));
+        let bf1 = Battlefield::new(2, 3).unwrap();
+        let bf2 = bf1.clone();
+        let battlefields = vec!(bf1, bf2);
+
+        let game = Game::new(ship_types, battlefields);
+        assert_eq!(2, game.width());
+        assert_eq!(3, game.height());
+    }
+
+    #[test]
+    fn should_tell_whos_turn_it_is() {
+        let mut game = build_test_game();
+
+        assert_eq!(P1, game.current_player());
+        game.shoot(P2, 2, 2).unwrap();
+        assert_eq!(P2, game.current_player());
+    }
+
+    #[test]
+    fn should_respect_order_of_play() {
+        let mut game = build_test_game();
+
+        assert_eq!(Err(NotThisPlayersTurn), game.shoot(P1, 0, 0));
+        game.shoot(P2, 2, 2).unwrap();
+        assert_eq!(Err(NotThisPlayersTurn), game.shoot(P2, 0, 0));
+        game.shoot(P1, 0, 0).unwrap();
+    }
+
+    #[test]
+    fn a_hit_lets_you_shoot_again() {
+        let mut game = build_test_game();
+
+        // single hit
+        assert_eq!(P1, game.current_player());
+        game.shoot(P2, 0, 0).unwrap();
+        assert_eq!(P1, game.current_player());
+        game.shoot(P2, 2, 2).unwrap();
+        assert_eq!(P2, game.current_player());
+
+        // destroyed ship
+        game.shoot(P1, 2, 2).unwrap();
+        game.shoot(P2, 1, 0).unwrap();
+        assert_eq!(P1, game.current_player());
+    }
+
+    #[test]
+    fn shooting_out_of_bounds_is_an_error() {
+        let mut game = build_test_game();
+
+        assert_eq!(Err(OutOfBounds), game.shoot(P2, 3, 0));
+        assert_eq!(Err(OutOfBounds), game.shoot(P2, 0, 3));
+        game.shoot(P2, 0, 0).unwrap();
+    }
+
+    #[test]
+    fn shooting_at_empty_cells_is_a_miss() {
+        let mut game = build_test_game();
+
+        assert_eq!(Ok(Miss), game.shoot(P2, 1, 1));
+        assert_eq!(Ok(Miss), game.shoot(P1, 1, 1));
+    }
+
+    #[test]
+    fn shooting_at_filled_cells_is_a_hit() {
+        let mut game = build_test_game();
+
+        assert_eq!(Ok(Hit), game.shoot(P2, 0, 0));
+    }
+
+    #[test]
+    fn destroying_a_ship_returns_ship_destroyed() {
+        let mut game = build_test_game();
+
+        game.shoot(P2, 0, 0).unwrap();
+        assert_eq!(Ok(Destroyed), game.shoot(P2, 1, 0));
+    }
+
+    #[test]
+    fn destroying_last_ship_wins_game() {
+        let mut game = build_test_game();
+
+        game.shoot(P2, 0, 0).unwrap();
+        game.shoot(P2, 1, 0).unwrap();
+        assert_eq!(Ok(WinningShot), game.shoot(P2, 0, 1));
+    }
+
+    #[test]
+    fn can_get_cell_status_for_owner() {
+        let mut game = build_test_game();
+
+        assert_eq!(CellStatus::Empty, game.get_cell(P2, 2, 2));
+        assert_eq!(CellStatus::Ship, game.get_cell(P2, 0, 0));
+        game.shoot(P2, 0, 0).unwrap();
+        assert_eq!(CellStatus::Hit, game.get_cell(P2, 0, 0));
+    }
+
+    #[test]
+    fn owner_cell_status_doesnt_show_misses() {
+        let mut game = build_test_game();
+
+        game.shoot(P2, 2, 2).unwrap();
+        assert_eq!(CellStatus::Empty, game.get_cell(P2, 2, 2));
+    }
+
+    #[test]
+    fn can_get_cell_status_for_opponent() {
+        let mut game = build_test_game();
+
+        assert_eq!(CellStatus::Empty, game.get_opponent_cell(P2, 0, 0));
+        game.shoot(P2, 0, 0).unwrap();
+        assert_eq!(CellStatus::Hit, game.get_opponent_cell(P2, 0, 0));
+        game.shoot(P2, 2, 2).unwrap();
+        assert_eq!(CellStatus::Miss, game.get_opponent_cell(P2, 2, 2));
+    }
+
+    #[test]
+    fn should_return_contained_ship_types() {
+        let game = build_test_game();
+        let mut types = game.ship_types();
+
+        assert_eq!(2, types.len());
+        types.clear();
+        assert_eq!(2, game.ship_types().len());
+    }
+
+    #[test]
+    fn should_not_allow_shots_after_game_ends() {
+        let mut game = build_test_game();
+        game.shoot(P2, 0, 0).unwrap();
+        game.shoot(P2, 1, 0).unwrap();
+        game.shoot(P2, 0, 1).unwrap();
+
+        assert_eq!(Err(GameOver), game.shoot(P2, 2, 2));
+        assert_eq!(Some(P1), game.get_winner());
+
+        let mut game = build_test_game();
+        game.shoot(P2, 2, 2).unwrap();
+        game.shoot(P1, 0, 0).unwrap();
+        game.shoot(P1, 1, 0).unwrap();
+        game.shoot(P1, 0, 1).unwrap();
+
+        assert_eq!(Err(GameOver), game.shoot(P1, 2, 2));
+        assert_eq!(Some(P2), game.get_winner());
+    }
+
+    #[test]
+    fn should_record_outcome_when_fleet_destroyed() {
+        let mut game = build_test_game();
+        assert_eq!(None, game.outcome());
+
+        game.shoot(P2, 0, 0).unwrap();
+        game.shoot(P2, 1, 0).unwrap();
+        game.shoot(P2, 0, 1).unwrap();
+        assert_eq!(Some(GameOutcome::new(Some(P1), EndReason::FleetDestroyed)), game.outcome());
+    }
+
+    #[test]
+    fn resigning_ends_the_game() {
+        let mut game = build_test_game();
+
+        assert_eq!(Ok(()), game.resign(P1));
+        assert_eq!(Some(GameOutcome::new(Some(P2), EndReason::Resignation)), game.outcome());
+        assert_eq!(Some(P2), game.get_winner());
+        assert_eq!(Err(GameOver), game.shoot(P2, 0, 0));
+        assert_eq!(Err(ResignError::GameOver), game.resign(P2));
+    }
+
+    #[test]
+    fn accepting_a_draw_ends_the_game() {
+        let mut game = build_test_game();
+
+        assert_eq!(Err(DrawError::NoDrawOffered), game.accept_draw(P2));
+        assert_eq!(Ok(()), game.offer_draw(P1));
+        assert_eq!(Some(P1), game.draw_offer());
+        assert_eq!(Err(DrawError::NoDrawOffered), game.accept_draw(P1));
+        assert_eq!(Ok(()), game.accept_draw(P2));
+
+        let outcome = game.outcome().unwrap();
+        assert!(outcome.is_draw());
+        assert_eq!(EndReason::AgreedDraw, outcome.reason());
+        assert_eq!(None, game.get_winner());
+        assert_eq!(Err(GameOver), game.shoot(P2, 0, 0));
+        assert_eq!(Err(DrawError::GameOver), game.offer_draw(P1));
+    }
+
+    #[test]
+    fn mutual_draw_offers_end_the_game() {
+        let mut game = build_test_game();
+
+        game.offer_draw(P2).unwrap();
+        game.offer_draw(P1).unwrap();
+        assert_eq!(Some(EndReason::AgreedDraw), game.outcome().map(|o| o.reason()));
+    }
+
+    #[test]
+    fn draw_offers_can_be_declined_or_expire() {
+        let mut game = build_test_game();
+
+        game.offer_draw(P1).unwrap();
+        assert_eq!(Ok(()), game.decline_draw(P2));
+        assert_eq!(None, game.draw_offer());
+
+        game.offer_draw(P1).unwrap();
+        game.shoot(P2, 2, 2).unwrap();
+        assert_eq!(Err(DrawError::NoDrawOffered), game.accept_draw(P2));
+        assert_eq!(None, game.outcome());
+    }
+
+    #[test]
+    fn shot_limit_ends_the_game() {
+        let mut pregame = build_test_pregame();
+        pregame.set_shot_limit(2);
+        let mut game = pregame.start().unwrap();
+
+        game.shoot(P2, 0, 0).unwrap();
+        game.shoot(P2, 1, 0).unwrap();
+        assert_eq!(P2, game.current_player());
+        game.shoot(P1, 2, 2).unwrap();
+        assert_eq!(None, game.outcome());
+        game.shoot(P1, 0, 0).unwrap();
+
+        assert_eq!(Some(GameOutcome::new(Some(P1), EndReason::ShotLimit)), game.outcome());
+        assert_eq!(Err(GameOver), game.shoot(P1, 0, 1));
+    }
+
+    #[test]
+    fn scans_respect_the_shot_limit() {
+        let mut pregame = build_test_pregame();
+        pregame.set_shot_limit(1);
+        pregame.set_recon_charges(1);
+        let mut game = pregame.start().unwrap();
+
+        game.shoot(P2, 2, 2).unwrap();
+        game.scan(P1, Scan::Row(2)).unwrap();
+        assert_eq!(P2, game.current_player());
+        assert_eq!(Err(NotThisPlayersTurn), game.shoot(P2, 2, 1));
+
+        game.shoot(P1, 2, 2).unwrap();
+        assert_eq!(Some(GameOutcome::new(None, EndReason::ShotLimit)), game.outcome());
+    }
+
+    #[test]
+    fn moves_respect_the_shot_limit() {
+        let mut pregame = build_test_pregame();
+        pregame.set_shot_limit(1);
+        pregame.set_ship_movement(true);
+        let mut game = pregame.start().unwrap();
+        let corvette = game.ship_types()[0].clone();
+
+        game.shoot(P2, 2, 2).unwrap();
+        game.move_ship(P2, &corvette, Shift(Right, 1)).unwrap();
+        assert_eq!(P2, game.current_player());
+
+        game.shoot(P1, 2, 2).unwrap();
+        assert_eq!(Some(GameOutcome::new(None, EndReason::ShotLimit)), game.outcome());
+    }
+
+    #[test]
+    fn shooting_a_hit_cell_again_deals_no_damage() {
+        let mut game = build_test_game();
+
+        assert_eq!(Ok(Hit), game.shoot(P2, 0, 0));
+        assert_eq!(Ok(Hit), game.shoot(P2, 0, 0));
+        assert_eq!(Ok(Destroyed), game.shoot(P2, 1, 0));
+        assert_eq!(Ok(Hit), game.shoot(P2, 1, 0));
+    }
+
+    #[test]
+    fn bombs_hit_the_surrounding_cells() {
+        let mut game = build_weapon_test_game();
+
+        let impacts = game.fire(P2, Weapon::Bomb, 1, 2).unwrap();
+        assert_eq!(vec!(
+            Impact::new(0, 1, Destroyed),
+            Impact::new(1, 1, Miss),
+            Impact::new(2, 1, Miss),
+            Impact::new(0, 2, Miss),
+            Impact::new(1, 2, Miss),
+            Impact::new(2, 2, Miss),
+        ), impacts);
+        assert_eq!(P1, game.current_player());
+        assert_eq!(CellStatus::Miss, game.get_opponent_cell(P2, 1, 2));
+    }
+
+    #[test]
+    fn cross_strikes_hit_the_orthogonal_neighbours() {
+        let mut game = build_weapon_test_game();
+
+        let impacts = game.fire(P2, Weapon::CrossStrike, 2, 1).unwrap();
+        assert_eq!(vec!(
+            Impact::new(2, 0, Miss),
+            Impact::new(1, 1, Miss),
+            Impact::new(2, 1, Miss),
+            Impact::new(2, 2, Miss),
+        ), impacts);
+        assert_eq!(P2, game.current_player());
+    }
+
+    #[test]
+    fn cross_strikes_hit_all_hex_neighbours() {
+        let mut pregame = build_shaped_test_pregame(Topology::Rectangular, Grid::Hexagonal);
+        pregame.set_weapon_uses(Weapon::CrossStrike, 1);
+        let mut game = pregame.start().unwrap();
+
+        let impacts = game.fire(P2, Weapon::CrossStrike, 1, 1).unwrap();
+        let coords: Vec<(usize, usize)> = impacts.iter().map(|i| (i.x(), i.y())).collect();
+        assert_eq!(vec!((1, 0), (2, 0), (0, 1), (1, 1), (2, 1), (0, 2), (1, 2)), coords);
+    }
+
+    #[test]
+    fn torpedoes_stop_at_the_first_ship() {
+        let mut pregame = build_test_pregame();
+        pregame.set_weapon_uses(Weapon::Torpedo, 2);
+        let mut game = pregame.start().unwrap();
+        game.shoot(P2, 2, 2).unwrap();
+
+        let impacts = game.fire(P1, Weapon::Torpedo, 1, 0).unwrap();
+        assert_eq!(vec!(Impact::new(1, 0, Hit)), impacts);
+
+        let impacts = game.fire(P1, Weapon::Torpedo, 1, 1).unwrap();
+        assert_eq!(vec!(Impact::new(1, 1, Miss), Impact::new(2, 1, Miss)), impacts);
+        assert_eq!(P1, game.current_player());
     }
 
     #[test]
-    fn destroying_a_ship_returns_ship_destroyed() {
-        let mut game = build_test_game();
+    fn torpedoes_stop_at_the_first_mine() {
+        let mut pregame = build_test_pregame();
+        pregame.set_weapon_uses(Weapon::Torpedo, 1);
+        pregame.set_mines(1, MinePenalty::LoseNextTurn);
+        pregame.place_mine(P1, 1, 2).unwrap();
+        pregame.place_mine(P2, 1, 2).unwrap();
+        let mut game = pregame.start().unwrap();
 
-        game.shoot(P2, 0, 0).unwrap();
+        let impacts = game.fire(P2, Weapon::Torpedo, 0, 2).unwrap();
+        assert_eq!(vec!(Impact::new(0, 2, Miss), Impact::new(1, 2, Mine)), impacts);
+    }
+
+    #[test]
+    fn weapons_wrap_around_toroidal_battlefields() {
+        let mut pregame = build_shaped_test_pregame(Topology::Toroidal, Grid::Square);
+        pregame.set_weapon_uses(Weapon::Torpedo, 1);
+        pregame.set_weapon_uses(Weapon::CrossStrike, 1);
+        let mut game = pregame.start().unwrap();
+
+        let impacts = game.fire(P2, Weapon::Torpedo, 1, 2).unwrap();
+        assert_eq!(vec!(Impact::new(1, 2, Miss), Impact::new(2, 2, Miss), Impact::new(0, 2, Miss)), impacts);
+
+        let impacts = game.fire(P1, Weapon::CrossStrike, 0, 2).unwrap();
+        assert_eq!(vec!(
+            Impact::new(0, 1, Destroyed),
+            Impact::new(2, 2, Miss),
+            Impact::new(0, 2, Miss),
+            Impact::new(1, 2, Miss),
+            Impact::new(0, 0, Hit),
+        ), impacts);
+    }
+
+    #[test]
+    fn bombs_hit_hex_neighbours() {
+        let mut pregame = build_shaped_test_pregame(Topology::Rectangular, Grid::Hexagonal);
+        pregame.set_weapon_uses(Weapon::Bomb, 1);
+        let mut game = pregame.start().unwrap();
+
+        let impacts = game.fire(P2, Weapon::Bomb, 1, 1).unwrap();
+        let coords: Vec<(usize, usize)> = impacts.iter().map(|i| (i.x(), i.y())).collect();
+        assert_eq!(vec!((1, 0), (2, 0), (0, 1), (1, 1), (2, 1), (0, 2), (1, 2)), coords);
+    }
+
+    #[test]
+    fn ships_rotate_through_hex_axes() {
+        let mut pregame = PreGame::new(3, 3).unwrap();
+        pregame.set_grid(Grid::Hexagonal).unwrap();
+        pregame.set_ship_movement(true);
+        let corvette = pregame.add_ship_type("Corvette", 2).unwrap();
+        pregame.place_ship(P1, &corvette, 1, 1, Horizontal).unwrap();
+        pregame.place_ship(P2, &corvette, 0, 0, Horizontal).unwrap();
+        let mut game = pregame.start().unwrap();
+
+        game.move_ship(P1, &corvette, Rotate).unwrap();
+        assert_eq!(CellStatus::Ship, game.get_cell(P1, 2, 0));
+        game.shoot(P1, 2, 2).unwrap();
+        game.move_ship(P1, &corvette, Rotate).unwrap();
+        assert_eq!(CellStatus::Ship, game.get_cell(P1, 1, 2));
+        assert_eq!(CellStatus::Empty, game.get_cell(P1, 2, 0));
+    }
+
+    #[test]
+    fn ships_shift_along_hex_axes() {
+        let mut pregame = PreGame::new(3, 3).unwrap();
+        pregame.set_grid(Grid::Hexagonal).unwrap();
+        pregame.set_ship_movement(true);
+        let corvette = pregame.add_ship_type("Corvette", 2).unwrap();
+        pregame.place_ship(P1, &corvette, 1, 1, Horizontal).unwrap();
+        pregame.place_ship(P2, &corvette, 0, 0, Horizontal).unwrap();
+        let mut game = pregame.start().unwrap();
+
+        assert_eq!(Err(MoveError::OutOfBounds), game.move_ship(P1, &corvette, Shift(UpRight, 1)));
+        assert_eq!(Ok(()), game.move_ship(P1, &corvette, Shift(DownLeft, 1)));
+        assert_eq!(CellStatus::Ship, game.get_cell(P1, 0, 2));
+        assert_eq!(CellStatus::Ship, game.get_cell(P1, 1, 2));
+        assert_eq!(CellStatus::Empty, game.get_cell(P1, 2, 1));
+    }
+
+    #[test]
+    fn depth_charges_reach_submerged_ships() {
+        let mut pregame = PreGame::new(3, 3).unwrap();
+        pregame.set_weapon_uses(Weapon::DepthCharge, 1);
+        let corvette = pregame.add_ship_type("Corvette", 2).unwrap();
+        let submarine = pregame.add_custom_ship_type(ShipType::new(0, "Submarine", 1).with_layer(Submerged)).unwrap();
+        for &player in &[P1, P2] {
+            pregame.place_ship(player, &corvette, 0, 0, Horizontal).unwrap();
+            pregame.place_ship(player, &submarine, 0, 0, Horizontal).unwrap();
+        }
+        let mut game = pregame.start().unwrap();
+
+        assert_eq!(Ok(Hit), game.shoot(P2, 0, 0));
+        assert_eq!(CellStatus::Ship, game.get_layer_cell(P2, Submerged, 0, 0));
+        assert_eq!(CellStatus::Empty, game.get_opponent_layer_cell(P2, Submerged, 0, 0));
+        assert_eq!(Ok(Destroyed), game.shoot_layer(P2, Submerged, 0, 0));
+        assert_eq!(CellStatus::Hit, game.get_opponent_layer_cell(P2, Submerged, 0, 0));
+        assert_eq!(Err(WeaponUnavailable), game.shoot_layer(P2, Submerged, 1, 0));
+        assert_eq!(Ok(WinningShot), game.shoot(P2, 1, 0));
+    }
+
+    #[test]
+    fn armored_segments_take_several_hits() {
+        let mut pregame = PreGame::new(3, 3).unwrap();
+        let corvette = pregame.add_custom_ship_type(ShipType::new(0, "Corvette", 2).with_armor(2)).unwrap();
+        let submarine = pregame.add_ship_type("Submarine", 1).unwrap();
+        for &player in &[P1, P2] {
+            pregame.place_ship(player, &corvette, 0, 0, Horizontal).unwrap();
+            pregame.place_ship(player, &submarine, 0, 1, Horizontal).unwrap();
+        }
+        let mut game = pregame.start().unwrap();
+
+        assert_eq!(Ok(Armored), game.shoot(P2, 0, 0));
+        assert_eq!(P1, game.current_player());
+        assert_eq!(CellStatus::Armored, game.get_opponent_cell(P2, 0, 0));
+        assert_eq!(CellStatus::Armored, game.get_cell(P2, 0, 0));
+        assert_eq!(Ok(Hit), game.shoot(P2, 0, 0));
+        assert_eq!(CellStatus::Hit, game.get_opponent_cell(P2, 0, 0));
+        assert_eq!(Ok(Hit), game.shoot(P2, 0, 0));
+        assert_eq!(Ok(Armored), game.shoot(P2, 1, 0));
         assert_eq!(Ok(Destroyed), game.shoot(P2, 1, 0));
     }
 
     #[test]
-    fn destroying_last_ship_wins_game() {
-        let mut game = build_test_game();
+    fn decoys_are_hit_but_never_destroyed() {
+        let mut pregame = build_decoy_test_pregame();
+        let mut game = pregame.start().unwrap();
 
-        game.shoot(P2, 0, 0).unwrap();
-        game.shoot(P2, 1, 0).unwrap();
-        assert_eq!(Ok(WinningShot), game.shoot(P2, 0, 1));
+        assert_eq!(Ok(Hit), game.shoot(P2, 0, 2));
+        assert_eq!(Ok(Hit), game.shoot(P2, 1, 2));
+        assert_eq!(CellStatus::Hit, game.get_opponent_cell(P2, 1, 2));
+        assert_eq!(Ok(WinningShot), game.shoot(P2, 0, 0));
+
+        pregame = build_decoy_test_pregame();
+        pregame.set_decoy_reveal(true);
+        let mut game = pregame.start().unwrap();
+
+        game.shoot(P2, 0, 2).unwrap();
+        assert_eq!(CellStatus::Hit, game.get_opponent_cell(P2, 0, 2));
+        game.shoot(P2, 1, 2).unwrap();
+        assert_eq!(CellStatus::Decoy, game.get_opponent_cell(P2, 0, 2));
+        assert_eq!(CellStatus::Decoy, game.get_opponent_cell(P2, 1, 2));
     }
 
     #[test]
-    fn can_get_cell_status_for_owner() {
-        let mut game = build_test_game();
+    fn simultaneous_rounds_resolve_together() {
+        let mut pregame = build_test_pregame();
+        pregame.set_simultaneous_turns(true);
+        let mut game = pregame.start().unwrap();
 
-        assert_eq!(CellStatus::Empty, game.get_cell(P2, 2, 2));
-        assert_eq!(CellStatus::Ship, game.get_cell(P2, 0, 0));
-        game.shoot(P2, 0, 0).unwrap();
-        assert_eq!(CellStatus::Hit, game.get_cell(P2, 0, 0));
+        assert_eq!(Err(NotThisPlayersTurn), game.shoot(P2, 0, 0));
+        assert_eq!(Err(ResolveError::NotAllCommitted), game.resolve_round());
+        assert_eq!(Ok(()), game.commit_shot(P1, 0, 0));
+        assert_eq!(Err(CommitError::AlreadyCommitted), game.commit_shot(P1, 1, 0));
+        assert_eq!(Err(CommitError::OutOfBounds), game.commit_shot(P2, 3, 0));
+        assert!(!game.has_committed(P2));
+        assert_eq!(Ok(()), game.commit_shot(P2, 2, 2));
+
+        let round = game.resolve_round().unwrap();
+        assert_eq!(Some(Hit), round.result(P1));
+        assert_eq!(Some(Miss), round.result(P2));
+        assert!(!game.has_committed(P1));
+        assert_eq!(CellStatus::Hit, game.get_opponent_cell(P2, 0, 0));
     }
 
     #[test]
-    fn owner_cell_status_doesnt_show_misses() {
-        let mut game = build_test_game();
+    fn simultaneous_rounds_can_end_in_a_draw() {
+        let mut pregame = build_test_pregame();
+        pregame.set_simultaneous_turns(true);
+        let mut game = pregame.start().unwrap();
 
-        game.shoot(P2, 2, 2).unwrap();
-        assert_eq!(CellStatus::Empty, game.get_cell(P2, 2, 2));
+        for &(x, y) in &[(0, 0), (1, 0), (0, 1)] {
+            game.commit_shot(P1, x, y).unwrap();
+            game.commit_shot(P2, x, y).unwrap();
+            game.resolve_round().unwrap();
+        }
+
+        assert_eq!(Some(GameOutcome::new(None, EndReason::FleetDestroyed)), game.outcome());
+        assert_eq!(Err(CommitError::GameOver), game.commit_shot(P1, 2, 2));
     }
 
     #[test]
-    fn can_get_cell_status_for_opponent() {
+    fn slow_players_sit_out_simultaneous_rounds() {
+        let mut pregame = build_test_pregame();
+        pregame.set_simultaneous_turns(true);
+        let clock = Arc::new(ManualClock::new());
+        let control = TimeControl::new(Some(Duration::from_secs(10)), None, Duration::from_secs(0)).unwrap();
+        pregame.set_time_control(control, clock.clone());
+        let mut game = pregame.start().unwrap();
+
+        game.commit_shot(P1, 0, 0).unwrap();
+        clock.advance(Duration::from_secs(11));
+        assert_eq!(Err(CommitError::SittingOut), game.commit_shot(P2, 0, 0));
+        let round = game.resolve_round().unwrap();
+        assert_eq!((Some(Hit), None), (round.result(P1), round.result(P2)));
+
+        assert_eq!(Ok(()), game.commit_shot(P2, 0, 0));
+    }
+
+    #[test]
+    fn simultaneous_rounds_respect_the_time_banks() {
+        let mut pregame = build_test_pregame();
+        pregame.set_simultaneous_turns(true);
+        let clock = Arc::new(ManualClock::new());
+        let control = TimeControl::new(None, Some(Duration::from_secs(60)), Duration::from_secs(0)).unwrap();
+        pregame.set_time_control(control, clock.clone());
+        let mut game = pregame.start().unwrap();
+
+        clock.advance(Duration::from_secs(20));
+        game.commit_shot(P1, 2, 2).unwrap();
+        clock.advance(Duration::from_secs(10));
+        game.commit_shot(P2, 2, 2).unwrap();
+        game.resolve_round().unwrap();
+        assert_eq!(Some(Duration::from_secs(40)), game.remaining_time(P1));
+        assert_eq!(Some(Duration::from_secs(30)), game.remaining_time(P2));
+
+        clock.advance(Duration::from_secs(35));
+        assert_eq!(Some(Duration::from_secs(5)), game.remaining_time(P1));
+        assert_eq!(Err(ResolveError::GameOver), game.resolve_round());
+        assert_eq!(Some(GameOutcome::new(Some(P1), EndReason::Timeout)), game.outcome());
+    }
+
+    #[test]
+    fn commits_require_simultaneous_turns() {
         let mut game = build_test_game();
+        assert_eq!(Err(CommitError::NotSimultaneous), game.commit_shot(P1, 0, 0));
+        assert_eq!(Err(ResolveError::NotSimultaneous), game.resolve_round());
+    }
 
-        assert_eq!(CellStatus::Empty, game.get_opponent_cell(P2, 0, 0));
+    #[test]
+    fn firepower_follows_the_surviving_fleet() {
+        let mut pregame = build_test_pregame();
+        pregame.set_firepower(true);
+        let mut game = pregame.start().unwrap();
+
+        assert_eq!(2, game.firepower(P1));
+        assert_eq!(Some(2), game.shots_left_in_turn());
+        assert_eq!(Ok(Destroyed), game.shoot(P2, 0, 1));
+        assert_eq!(Some(1), game.shots_left_in_turn());
+        assert_eq!(Ok(Hit), game.shoot(P2, 0, 0));
+        assert_eq!(P2, game.current_player());
+        assert_eq!(1, game.firepower(P2));
+        assert_eq!(Some(1), game.shots_left_in_turn());
+
+        assert_eq!(Ok(Miss), game.shoot(P1, 2, 2));
+        assert_eq!(P1, game.current_player());
+        assert_eq!(Some(2), game.shots_left_in_turn());
+    }
+
+    #[test]
+    fn weapons_can_win_the_game() {
+        let mut game = build_weapon_test_game();
+        let impacts = game.fire(P2, Weapon::Bomb, 0, 0).unwrap();
+        assert_eq!(Some(&Impact::new(0, 1, WinningShot)), impacts.iter().find(|i| i.result() == WinningShot));
+        assert_eq!(Some(P1), game.get_winner());
+    }
+
+    #[test]
+    fn weapon_uses_are_limited_per_player() {
+        let mut game = build_weapon_test_game();
+
+        assert_eq!(1, game.weapon_uses(P1, Weapon::Bomb));
+        assert_eq!(0, game.weapon_uses(P1, Weapon::Torpedo));
+        assert_eq!(Err(WeaponUnavailable), game.fire(P2, Weapon::Torpedo, 0, 0));
+        assert_eq!(Err(OutOfBounds), game.fire(P2, Weapon::Bomb, 3, 0));
+        assert_eq!(1, game.weapon_uses(P1, Weapon::Bomb));
+
+        game.fire(P2, Weapon::Bomb, 2, 2).unwrap();
+        assert_eq!(0, game.weapon_uses(P1, Weapon::Bomb));
+        assert_eq!(1, game.weapon_uses(P2, Weapon::Bomb));
+    }
+
+    #[test]
+    fn area_scans_detect_ships() {
+        let mut game = build_recon_test_game();
+
+        assert_eq!(Ok(ScanOk::Contact), game.scan(P2, Scan::Area { x: 0, y: 0, size: 2 }));
+        assert_eq!(P2, game.current_player());
+        assert_eq!(CellStatus::Contact, game.get_opponent_cell(P2, 1, 1));
+        assert_eq!(CellStatus::Empty, game.get_opponent_cell(P2, 2, 2));
+
+        assert_eq!(Ok(ScanOk::NoContact), game.scan(P1, Scan::Area { x: 1, y: 1, size: 2 }));
+        assert_eq!(CellStatus::Clear, game.get_opponent_cell(P1, 2, 2));
+        assert_eq!(CellStatus::Empty, game.get_opponent_cell(P1, 0, 0));
+    }
+
+    #[test]
+    fn line_scans_count_ship_cells() {
+        let mut game = build_recon_test_game();
+
+        assert_eq!(Ok(ScanOk::ShipCells(2)), game.scan(P2, Scan::Row(0)));
+        assert_eq!(Ok(ScanOk::ShipCells(0)), game.scan(P1, Scan::Column(2)));
+        assert_eq!(CellStatus::Clear, game.get_opponent_cell(P1, 2, 1));
+        assert_eq!(CellStatus::Empty, game.get_opponent_cell(P2, 2, 0));
+        assert_eq!(vec!((Scan::Row(0), ScanOk::ShipCells(2))), game.scans(P2));
+    }
+
+    #[test]
+    fn scanned_cells_still_show_shots() {
+        let mut game = build_recon_test_game();
+
+        game.scan(P2, Scan::Area { x: 0, y: 0, size: 2 }).unwrap();
+        game.shoot(P1, 2, 2).unwrap();
         game.shoot(P2, 0, 0).unwrap();
+        game.shoot(P2, 1, 1).unwrap();
         assert_eq!(CellStatus::Hit, game.get_opponent_cell(P2, 0, 0));
+        assert_eq!(CellStatus::Miss, game.get_opponent_cell(P2, 1, 1));
+        assert_eq!(CellStatus::Contact, game.get_opponent_cell(P2, 1, 0));
+    }
+
+    #[test]
+    fn scans_are_validated() {
+        let mut game = build_recon_test_game();
+
+        assert_eq!(Err(ScanError::NotThisPlayersTurn), game.scan(P1, Scan::Row(0)));
+        assert_eq!(Err(ScanError::IllegalScanSize), game.scan(P2, Scan::Area { x: 0, y: 0, size: 0 }));
+        assert_eq!(Err(ScanError::OutOfBounds), game.scan(P2, Scan::Area { x: 2, y: 2, size: 2 }));
+        assert_eq!(Err(ScanError::OutOfBounds), game.scan(P2, Scan::Row(3)));
+        assert_eq!(1, game.recon_charges(P1));
+
+        game.scan(P2, Scan::Column(0)).unwrap();
+        game.shoot(P1, 2, 2).unwrap();
+        assert_eq!(0, game.recon_charges(P1));
+        assert_eq!(Err(ScanError::NoChargesLeft), game.scan(P2, Scan::Row(0)));
+    }
+
+    #[test]
+    fn mines_can_hit_the_shooters_own_ship() {
+        let mut game = build_mine_test_game(MinePenalty::HitOwnShip);
+
+        assert_eq!(Ok(Mine), game.shoot(P2, 2, 2));
+        assert_eq!(P2, game.current_player());
+        assert_eq!(CellStatus::Mine, game.get_opponent_cell(P2, 2, 2));
+        let own_hits = [(0, 0), (1, 0), (0, 1)].iter()
+            .filter(|&&(x, y)| game.get_cell(P1, x, y) == CellStatus::Hit)
+            .count();
+        assert_eq!(1, own_hits);
+
+        game.shoot(P1, 1, 1).unwrap();
+        assert_eq!(Ok(Mine), game.shoot(P2, 2, 2));
+        assert_eq!(P2, game.current_player());
+        let own_hits = [(0, 0), (1, 0), (0, 1)].iter()
+            .filter(|&&(x, y)| game.get_cell(P1, x, y) == CellStatus::Hit)
+            .count();
+        assert_eq!(1, own_hits);
+    }
+
+    #[test]
+    fn mines_can_sink_the_shooters_last_ship() {
+        let mut pregame = PreGame::new(2, 2).unwrap();
+        let submarine = pregame.add_ship_type("Submarine", 1).unwrap();
+        pregame.set_mines(1, MinePenalty::HitOwnShip);
+        pregame.place_ship(P1, &submarine, 0, 0, Horizontal).unwrap();
+        pregame.place_ship(P2, &submarine, 0, 0, Horizontal).unwrap();
+        pregame.place_mine(P1, 1, 1).unwrap();
+        pregame.place_mine(P2, 1, 1).unwrap();
+        let mut game = pregame.start().unwrap();
+
+        assert_eq!(Ok(Mine), game.shoot(P2, 1, 1));
+        assert_eq!(Some(GameOutcome::new(Some(P2), EndReason::FleetDestroyed)), game.outcome());
+    }
+
+    #[test]
+    fn mines_can_cost_the_next_turn() {
+        let mut game = build_mine_test_game(MinePenalty::LoseNextTurn);
+
+        assert_eq!(Ok(Mine), game.shoot(P2, 2, 2));
+        assert_eq!(P2, game.current_player());
+        game.shoot(P1, 1, 1).unwrap();
+        assert_eq!(P2, game.current_player());
+        game.shoot(P1, 2, 1).unwrap();
+        assert_eq!(P1, game.current_player());
+    }
+
+    #[test]
+    fn ships_can_be_moved_instead_of_shooting() {
+        let (mut game, _, submarine) = build_movement_test_game();
+
+        let diagonal = Shift(DownLeft, 1);
+        assert_eq!(Err(MoveError::IllegalDirection), game.move_ship(P1, &submarine, diagonal));
+        assert_eq!(Ok(()), game.move_ship(P1, &submarine, Shift(Right, 2)));
+        assert_eq!(P2, game.current_player());
+        assert_eq!(CellStatus::Empty, game.get_cell(P1, 0, 1));
+        assert_eq!(CellStatus::Ship, game.get_cell(P1, 2, 1));
+        assert_eq!(Ok(Miss), game.shoot(P1, 0, 1));
+        assert_eq!(Ok(Miss), game.shoot(P2, 2, 2));
+        assert_eq!(Ok(Destroyed), game.shoot(P1, 2, 1));
+    }
+
+    #[test]
+    fn ships_can_be_rotated() {
+        let (mut game, corvette, _) = build_movement_test_game();
+
+        assert_eq!(Err(MoveError::CellOccupied), game.move_ship(P1, &corvette, Rotate));
         game.shoot(P2, 2, 2).unwrap();
-        assert_eq!(CellStatus::Miss, game.get_opponent_cell(P2, 2, 2));
+        game.move_ship(P2, &corvette, Shift(Right, 1)).unwrap();
+        game.shoot(P2, 1, 2).unwrap();
+        assert_eq!(Ok(()), game.move_ship(P2, &corvette, Rotate));
+        assert_eq!(CellStatus::Ship, game.get_cell(P2, 1, 0));
+        assert_eq!(CellStatus::Ship, game.get_cell(P2, 1, 1));
+        assert_eq!(CellStatus::Empty, game.get_cell(P2, 2, 0));
     }
 
     #[test]
-    fn should_return_contained_ship_types() {
-        let game = build_test_game();
-        let mut types = game.ship_types();
+    fn moving_onto_missed_cells_keeps_the_misses() {
+        let (mut game, _, submarine) = build_movement_test_game();
 
-        assert_eq!(2, types.len());
-        types.clear();
-        assert_eq!(2, game.ship_types().len());
+        game.shoot(P2, 1, 1).unwrap();
+        game.move_ship(P2, &submarine, Shift(Right, 1)).unwrap();
+        assert_eq!(CellStatus::Miss, game.get_opponent_cell(P2, 1, 1));
+        assert_eq!(CellStatus::Ship, game.get_cell(P2, 1, 1));
+        assert_eq!(Ok(Destroyed), game.shoot(P2, 1, 1));
+        assert_eq!(CellStatus::Hit, game.get_opponent_cell(P2, 1, 1));
     }
 
     #[test]
-    fn should_not_allow_shots_after_game_ends() {
+    fn ship_movement_is_validated() {
+        let (mut game, corvette, submarine) = build_movement_test_game();
+
+        assert_eq!(Err(MoveError::NotThisPlayersTurn), game.move_ship(P2, &submarine, Shift(Right, 1)));
+        assert_eq!(Err(MoveError::IllegalDistance), game.move_ship(P1, &submarine, Shift(Right, 3)));
+        assert_eq!(Err(MoveError::IllegalDistance), game.move_ship(P1, &submarine, Shift(Right, 0)));
+        assert_eq!(Err(MoveError::OutOfBounds), game.move_ship(P1, &submarine, Shift(Left, 1)));
+        assert_eq!(Err(MoveError::OutOfBounds), game.move_ship(P1, &corvette, Shift(Right, 2)));
+        assert_eq!(Err(MoveError::CellOccupied), game.move_ship(P1, &submarine, Shift(Up, 1)));
+        let unknown = Arc::new(ShipType::new(5, "Jetski", 1));
+        assert_eq!(Err(MoveError::UnknownShipType), game.move_ship(P1, &unknown, Rotate));
+
+        game.shoot(P2, 0, 0).unwrap();
+        game.shoot(P2, 2, 2).unwrap();
+        assert_eq!(Err(MoveError::ShipDamaged), game.move_ship(P2, &corvette, Shift(Down, 1)));
+        assert_eq!(Ok(()), game.move_ship(P2, &submarine, Shift(Down, 1)));
+
         let mut game = build_test_game();
+        let corvette = game.ship_types()[0].clone();
+        assert_eq!(Err(MoveError::MovementNotAllowed), game.move_ship(P1, &corvette, Rotate));
+    }
+
+    #[test]
+    fn exceeding_the_turn_limit_forfeits_the_turn() {
+        let clock = Arc::new(ManualClock::new());
+        let control = TimeControl::new(Some(Duration::from_secs(10)), None, Duration::from_secs(0)).unwrap();
+        let mut game = build_timed_test_game(control, clock.clone());
+
+        clock.advance(Duration::from_secs(11));
+        assert_eq!(Some(Duration::from_secs(0)), game.remaining_turn_time());
+        assert_eq!(Err(NotThisPlayersTurn), game.shoot(P2, 0, 0));
+        assert_eq!(P2, game.current_player());
+        assert_eq!(Some(Duration::from_secs(9)), game.remaining_turn_time());
+        assert_eq!(Ok(Hit), game.shoot(P1, 0, 0));
+    }
+
+    #[test]
+    fn timed_out_turns_reset_the_shots_of_the_turn() {
+        let mut pregame = build_test_pregame();
+        pregame.set_firepower(true);
+        let clock = Arc::new(ManualClock::new());
+        let control = TimeControl::new(Some(Duration::from_secs(10)), None, Duration::from_secs(0)).unwrap();
+        pregame.set_time_control(control, clock.clone());
+        let mut game = pregame.start().unwrap();
+
+        assert_eq!(Ok(Miss), game.shoot(P2, 2, 2));
+        clock.advance(Duration::from_secs(11));
+        game.check_time();
+        assert_eq!(P2, game.current_player());
+        assert_eq!(Some(2), game.shots_left_in_turn());
+    }
+
+    #[test]
+    fn timed_out_turns_respect_lost_turns() {
+        let mut pregame = build_test_pregame();
+        pregame.set_mines(1, MinePenalty::LoseNextTurn);
+        pregame.place_mine(P1, 2, 2).unwrap();
+        pregame.place_mine(P2, 2, 2).unwrap();
+        let clock = Arc::new(ManualClock::new());
+        let control = TimeControl::new(Some(Duration::from_secs(10)), None, Duration::from_secs(0)).unwrap();
+        pregame.set_time_control(control, clock.clone());
+        let mut game = pregame.start().unwrap();
+
+        assert_eq!(Ok(Mine), game.shoot(P2, 2, 2));
+        clock.advance(Duration::from_secs(11));
+        game.check_time();
+        assert_eq!(P2, game.current_player());
+        clock.advance(Duration::from_secs(11));
+        game.check_time();
+        assert_eq!(P1, game.current_player());
+    }
+
+    #[test]
+    fn timed_out_turns_skip_players_without_firepower() {
+        let mut pregame = PreGame::new(3, 3).unwrap();
+        let gunboat = pregame.add_ship_type("Gunboat", 1).unwrap();
+        let tanker = pregame.add_custom_ship_type(ShipType::new(0, "Tanker", 2).with_firepower(0)).unwrap();
+        for &player in &[P1, P2] {
+            pregame.place_ship(player, &gunboat, 0, 0, Horizontal).unwrap();
+            pregame.place_ship(player, &tanker, 0, 2, Horizontal).unwrap();
+        }
+        pregame.set_firepower(true);
+        let clock = Arc::new(ManualClock::new());
+        let control = TimeControl::new(Some(Duration::from_secs(10)), None, Duration::from_secs(0)).unwrap();
+        pregame.set_time_control(control, clock.clone());
+        let mut game = pregame.start().unwrap();
+
+        game.shoot(P2, 2, 2).unwrap();
+        assert_eq!(Ok(Destroyed), game.shoot(P1, 0, 0));
+        assert_eq!(P2, game.current_player());
+        clock.advance(Duration::from_secs(11));
+        game.check_time();
+        assert_eq!(P2, game.current_player());
+        assert_eq!(None, game.outcome());
+    }
+
+    #[test]
+    fn running_out_of_time_forfeits_the_game() {
+        let clock = Arc::new(ManualClock::new());
+        let control = TimeControl::new(None, Some(Duration::from_secs(60)), Duration::from_secs(0)).unwrap();
+        let mut game = build_timed_test_game(control, clock.clone());
+
+        clock.advance(Duration::from_secs(20));
+        game.shoot(P2, 2, 2).unwrap();
+        clock.advance(Duration::from_secs(60));
+        game.check_time();
+
+        assert_eq!(Some(GameOutcome::new(Some(P1), EndReason::Timeout)), game.outcome());
+        assert_eq!(Err(GameOver), game.shoot(P1, 0, 0));
+    }
+
+    #[test]
+    fn should_track_remaining_time() {
+        let clock = Arc::new(ManualClock::new());
+        let control = TimeControl::new(None, Some(Duration::from_secs(60)), Duration::from_secs(5)).unwrap();
+        let mut game = build_timed_test_game(control, clock.clone());
+
+        clock.advance(Duration::from_secs(20));
+        assert_eq!(Some(Duration::from_secs(40)), game.remaining_time(P1));
         game.shoot(P2, 0, 0).unwrap();
-        game.shoot(P2, 1, 0).unwrap();
-        game.shoot(P2, 0, 1).unwrap();
+        game.shoot(P2, 2, 2).unwrap();
+        assert_eq!(Some(Duration::from_secs(45)), game.remaining_time(P1));
+        assert_eq!(Some(Duration::from_secs(60)), game.remaining_time(P2));
+        assert_eq!(None, game.remaining_turn_time());
 
-        assert_eq!(Err(GameOver), game.shoot(P2, 2, 2));
-        assert_eq!(Some(P1), game.get_winner());
+        let game = build_test_game();
+        assert_eq!(None, game.remaining_time(P1));
+    }
+
+    #[test]
+    fn should_suggest_shots_from_visible_cells_only() {
+        let suggestion = |x| {
+            let mut pregame = PreGame::new(5, 5).unwrap();
+            let corvette = pregame.add_ship_type("Corvette", 2).unwrap();
+            pregame.place_ship(P1, &corvette, 0, 0, Horizontal).unwrap();
+            pregame.place_ship(P2, &corvette, x, 4, Horizontal).unwrap();
+            let mut game = pregame.start().unwrap();
+            game.shoot(P2, 2, 2).unwrap();
+            game.suggest_shot(P2)
+        };
+
+        let hint = suggestion(0).unwrap();
+        assert_eq!(HintReason::HighestDensity, hint.reason());
+        assert_eq!(Some(hint), suggestion(3));
 
         let mut game = build_test_game();
-        game.shoot(P2, 2, 2).unwrap();
-        game.shoot(P1, 0, 0).unwrap();
-        game.shoot(P1, 1, 0).unwrap();
-        game.shoot(P1, 0, 1).unwrap();
+        game.shoot(P2, 0, 0).unwrap();
+        let hint = game.suggest_shot(P1).unwrap();
+        assert_eq!(HintReason::AdjacentToHit, hint.reason());
+        game.resign(P1).unwrap();
+        assert_eq!(None, game.suggest_shot(P1));
+    }
 
-        assert_eq!(Err(GameOver), game.shoot(P1, 2, 2));
-        assert_eq!(Some(P2), game.get_winner());
+    #[test]
+    fn should_ignore_submerged_ships_in_suggestions() {
+        let suggestion = |with_submarine| {
+            let mut pregame = PreGame::new(5, 5).unwrap();
+            let corvette = pregame.add_ship_type("Corvette", 2).unwrap();
+            pregame.place_ship(P1, &corvette, 0, 0, Horizontal).unwrap();
+            pregame.place_ship(P2, &corvette, 2, 3, Horizontal).unwrap();
+            if with_submarine {
+                pregame.set_weapon_uses(Weapon::DepthCharge, 5);
+                let submarine = ShipType::new(0, "Submarine", 5).with_layer(Submerged);
+                let submarine = pregame.add_custom_ship_type(submarine).unwrap();
+                pregame.place_ship(P1, &submarine, 0, 4, Horizontal).unwrap();
+                pregame.place_ship(P2, &submarine, 0, 4, Horizontal).unwrap();
+            }
+            let mut game = pregame.start().unwrap();
+            for &(x, y) in &[(1, 0), (2, 1), (3, 2), (4, 3), (1, 4)] {
+                game.shoot(P2, x, y).unwrap();
+                game.shoot(P1, 4, 4).unwrap();
+            }
+            game.suggest_shot(P1)
+        };
+
+        assert_eq!(suggestion(false), suggestion(true));
+    }
+
+    #[test]
+    fn should_give_no_hints_on_toroidal_or_hexagonal_battlefields() {
+        let pregame = build_shaped_test_pregame(Topology::Toroidal, Grid::Square);
+        assert_eq!(None, pregame.start().unwrap().suggest_shot(P1));
+
+        let pregame = build_shaped_test_pregame(Topology::Rectangular, Grid::Hexagonal);
+        assert_eq!(None, pregame.start().unwrap().suggest_shot(P1));
+
+        assert!(build_test_pregame().start().unwrap().suggest_shot(P1).is_some());
+    }
+
+    fn build_timed_test_game(
+        control: TimeControl,
+        clock: Arc<ManualClock>,
+    ) -> Game {
+        let mut pregame = build_test_pregame();
+        pregame.set_time_control(control, clock);
+
+        pregame.start().unwrap()
+    }
+
+    fn build_decoy_test_pregame() -> PreGame {
+        let mut pregame = PreGame::new(3, 3).unwrap();
+        let submarine = pregame.add_ship_type("Submarine", 1).unwrap();
+        let decoy = pregame.add_custom_ship_type(ShipType::new(0, "Decoy", 2).with_decoy(true)).unwrap();
+        for &player in &[P1, P2] {
+            pregame.place_ship(player, &submarine, 0, 0, Horizontal).unwrap();
+            pregame.place_ship(player, &decoy, 0, 2, Horizontal).unwrap();
+        }
+
+        pregame
+    }
+
+    fn build_weapon_test_game() -> Game {
+        let mut pregame = build_test_pregame();
+        pregame.set_weapon_uses(Weapon::Bomb, 1);
+        pregame.set_weapon_uses(Weapon::CrossStrike, 1);
+
+        pregame.start().unwrap()
+    }
+
+    fn build_recon_test_game() -> Game {
+        let mut pregame = build_test_pregame();
+        pregame.set_recon_charges(1);
+
+        pregame.start().unwrap()
+    }
+
+    fn build_mine_test_game(penalty: MinePenalty) -> Game {
+        let mut pregame = build_test_pregame();
+        pregame.set_mines(1, penalty);
+        pregame.set_seed(7);
+        pregame.place_mine(P1, 2, 2).unwrap();
+        pregame.place_mine(P2, 2, 2).unwrap();
+
+        pregame.start().unwrap()
+    }
+
+    fn build_movement_test_game() -> (Game, Arc<ShipType>, Arc<ShipType>) {
+        let mut pregame = build_test_pregame();
+        pregame.set_ship_movement(true);
+        let game = pregame.start().unwrap();
+        let types = game.ship_types();
+
+        (game, types[0].clone(), types[1].clone())
     }
 
     fn build_test_game() -> Game {
+        build_test_pregame().start().unwrap()
+    }
+
+    fn build_test_pregame() -> PreGame {
+        build_shaped_test_pregame(Topology::Rectangular, Grid::Square)
+    }
+
+    fn build_shaped_test_pregame(
+        topology: Topology,
+        grid: Grid,
+    ) -> PreGame {
         let mut pregame = PreGame::new(3, 3).unwrap();
+        pregame.set_topology(topology).unwrap();
+        pregame.set_grid(grid).unwrap();
         let corvette = pregame.add_ship_type("Corvette", 2).unwrap();
         let submarine = pregame.add_ship_type("Submarine", 1).unwrap();
         pregame.place_ship(P1, &corvette, 0, 0 , Horizontal).unwrap();
@@ -383,6 +2218,6 @@ mod test {
         pregame.place_ship(P1, &submarine, 0, 1, Horizontal).unwrap();
         pregame.place_ship(P2, &submarine, 0, 1, Horizontal).unwrap();
 
-        pregame.start().unwrap()
+        pregame
     }
 }
@@ -62,19 +62,32 @@
 //!   ShootOk::Hit => println!("hit!"),
 //!   ShootOk::Miss => println!("miss!"),
 //!   ShootOk::Destroyed => println!("ship destroyed!"),
-//!   ShootOk::WinningShot => println!("you won!")
+//!   ShootOk::WinningShot => println!("you won!"),
+//!   ShootOk::Mine => println!("you hit a mine!"),
+//!   ShootOk::Armored => println!("the armor held!")
 //! }
 //! ```
 
 pub use self::game::Game;
 pub use self::pregame::PreGame;
+pub use self::puzzle::Puzzle;
 
+pub mod ai;
+pub mod analysis;
 pub mod common;
+pub mod environment;
+pub mod protocol;
 pub mod results;
+pub mod solitaire;
+pub mod time;
+pub mod tournament;
 
 mod battlefield;
 mod game;
 mod pregame;
+mod puzzle;
+mod random;
+mod rules;
 
 /// Dimension type for battleship.
 pub type Dimension = usize;
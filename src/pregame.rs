@@ -3,24 +3,38 @@
 use battlefield::Battlefield;
 use common::CellStatus;
 use common::Dimensional;
+use common::Grid;
+use common::Layer;
+use common::MinePenalty;
 use common::Orientation;
 use common::Player;
 use common::Player::*;
 use common::ShipType;
 use common::ShipTypeContainer;
+use common::Topology;
+use common::Weapon;
 use game::Game;
+use puzzle::Puzzle;
+use random::Rng;
 use results::GameError;
 use results::GameStartError;
 use results::GameStartError::*;
+use results::LayoutError;
 use results::PlaceError;
 use results::PlaceError::*;
+use results::PuzzleError;
+use results::PuzzleError::*;
 use results::ShipTypeError;
 use results::ShipTypeError::*;
+use rules::Rules;
 use std::cmp::max;
 use std::collections::HashSet;
 use std::sync::Arc;
 use super::Dimension;
 use super::ShipTypeId;
+use time::Clock;
+use time::TimeControl;
+use time::TurnClock;
 
 /// Builder type for a game of battleship.
 #[derive(PartialEq, Debug)]
@@ -29,7 +43,9 @@ pub struct PreGame {
     height: Dimension,
     ship_types: Vec<Arc<ShipType>>,
     placed_ships: HashSet<(Player, ShipTypeId)>,
+    placed_mines: HashSet<(Player, Dimension, Dimension)>,
     battlefields: Vec<Battlefield>,
+    rules: Rules,
 }
 
 /// Builder style struct for battleship.
@@ -52,7 +68,9 @@ impl PreGame {
             height: height,
             ship_types: Vec::new(),
             placed_ships: HashSet::new(),
+            placed_mines: HashSet::new(),
             battlefields: vec!(bf1, bf2),
+            rules: Rules::default(),
         })
     }
 
@@ -61,13 +79,258 @@ impl PreGame {
     /// # Errors
     /// * `NoShipsPlaced` if no player has placed any ships yet
     /// * `NotAllShipsPlaced` if not all ships have been placed yet
+    /// * `NotAllMinesPlaced` if not all mines have been placed yet
+    /// * `SubmergedShipsUnreachable` if there are fewer depth charges than it takes to destroy
+    ///   the submerged ships
+    /// * `OnlyDecoys` if all ship types are decoys
+    /// * `NoFirepower` if the firepower rule is enabled, but no real ship type has any firepower
+    #[allow(clippy::result_large_err)]
     pub fn start(self) -> Result<Game, (Self, GameStartError)> {
         if self.placed_ships.len() == 0 {
             Err((self, NoShipsPlaced))
         } else if self.placed_ships.len() != (2 * self.ship_types.len()) {
             Err((self, NotAllShipsPlaced))
+        } else if self.placed_mines.len() != (2 * self.rules.mines) {
+            Err((self, NotAllMinesPlaced))
+        } else if let Err(err) = self.check_fleet() {
+            Err((self, err))
         } else {
-            Ok(Game::new(self.ship_types, self.battlefields))
+            Ok(Game::with_rules(self.ship_types, self.battlefields, self.rules))
+        }
+    }
+
+    /// Checks the fleet against the rules, for both games and puzzles.
+    fn check_fleet(&self) -> Result<(), GameStartError> {
+        if self.rules.weapon_uses.get(&Weapon::DepthCharge).cloned().unwrap_or(0) < self.submerged_health() {
+            Err(SubmergedShipsUnreachable)
+        } else if self.ship_types.iter().all(|ship_type| ship_type.is_decoy()) {
+            Err(OnlyDecoys)
+        } else if self.rules.firepower && self.ship_types.iter().all(|ship_type| ship_type.is_decoy() || ship_type.firepower() == 0) {
+            Err(NoFirepower)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Consume this `PreGame` and provide a single-player `Puzzle` from it. The computer places
+    /// player 2's fleet at random, keeping any ships that have already been placed for player 2.
+    /// Player 1 doesn't need a fleet, they only shoot at player 2's battlefield.
+    /// # Parameters
+    /// * `shots` The number of shots player 1 may take to destroy the fleet.
+    ///
+    /// # Errors
+    /// * `NoShipTypes` if no ship types have been added
+    /// * `IllegalShotBudget` if `shots` is zero
+    /// * `FleetDoesNotFit` if the computer couldn't find room for all ships
+    /// * `IllegalFleet` if the fleet breaks a rule, see the fleet related errors of `start`
+    ///
+    /// # Examples
+    /// ```
+    /// # use lib_battleship::PreGame;
+    /// #
+    /// let mut pregame = PreGame::new(5, 5).unwrap();
+    /// pregame.add_ship_type("Corvette", 2).unwrap();
+    /// pregame.set_seed(42);
+    /// let mut puzzle = pregame.start_puzzle(10).unwrap();
+    /// puzzle.shoot(2, 2).unwrap();
+    /// ```
+    #[allow(clippy::result_large_err)]
+    pub fn start_puzzle(
+        mut self,
+        shots: usize,
+    ) -> Result<Puzzle, (Self, PuzzleError)> {
+        if self.ship_types.is_empty() {
+            return Err((self, NoShipTypes));
+        }
+        if shots == 0 {
+            return Err((self, IllegalShotBudget));
+        }
+        if let Err(err) = self.check_fleet() {
+            return Err((self, IllegalFleet(err)));
+        }
+
+        let mut rng = self.rules.seed.map(Rng::new).unwrap_or_else(Rng::from_time);
+        let mut bf = self.battlefield(P2).clone();
+        for ship_type in &self.ship_types {
+            if self.placed_ships.contains(&(P2, ship_type.id())) {
+                continue;
+            }
+            let positions = bf.free_positions(ship_type.length(), ship_type.layer());
+            if positions.is_empty() {
+                return Err((self, FleetDoesNotFit));
+            }
+            let position = positions[rng.below(positions.len())];
+            let coords = bf.ship_coords(ship_type.length(), position).unwrap();
+            bf.place_ship(ship_type.id(), ship_type.layer(), position, &coords);
+        }
+
+        *self.battlefield_mut(P2) = bf;
+        self.rules.single_player = true;
+        let game = Game::with_rules(self.ship_types, self.battlefields, self.rules);
+        Ok(Puzzle::new(game, shots))
+    }
+
+    /// Enables time controls for the game.
+    /// # Parameters
+    /// * `control` The time controls to enforce.
+    /// * `clock` The source of time, usually a `SystemClock`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lib_battleship::PreGame;
+    /// # use lib_battleship::time::SystemClock;
+    /// # use lib_battleship::time::TimeControl;
+    /// # use std::sync::Arc;
+    /// # use std::time::Duration;
+    /// #
+    /// let mut pregame = PreGame::new(10, 10).unwrap();
+    /// let control = TimeControl::new(
+    ///     Some(Duration::from_secs(30)),
+    ///     Some(Duration::from_secs(600)),
+    ///     Duration::from_secs(5),
+    /// ).unwrap();
+    /// pregame.set_time_control(control, Arc::new(SystemClock::new()));
+    /// ```
+    pub fn set_time_control(
+        &mut self,
+        control: TimeControl,
+        clock: Arc<dyn Clock>,
+    ) {
+        self.rules.turn_clock = Some(TurnClock::new(control, clock));
+    }
+
+    /// Limits the number of shots each player may fire. Once both players have used up their
+    /// shots, the game ends and the player who dealt more damage wins.
+    /// # Parameters
+    /// * `limit` The number of shots per player.
+    pub fn set_shot_limit(
+        &mut self,
+        limit: usize,
+    ) {
+        self.rules.shot_limit = Some(limit);
+    }
+
+    /// Sets how often each player may use `weapon` during the game. Weapons not configured
+    /// here can't be used at all.
+    /// # Parameters
+    /// * `weapon` The special weapon.
+    /// * `uses` The number of uses per player.
+    pub fn set_weapon_uses(
+        &mut self,
+        weapon: Weapon,
+        uses: usize,
+    ) {
+        self.rules.weapon_uses.insert(weapon, uses);
+    }
+
+    /// Sets how many recon scans each player may perform during the game. No scans are
+    /// allowed by default.
+    /// # Parameters
+    /// * `charges` The number of scans per player.
+    pub fn set_recon_charges(
+        &mut self,
+        charges: usize,
+    ) {
+        self.rules.recon_charges = charges;
+    }
+
+    /// Gives each player `count` naval mines to place on their battlefield, see `place_mine`.
+    /// # Parameters
+    /// * `count` The number of mines per player.
+    /// * `penalty` The penalty for shooting a mine.
+    pub fn set_mines(
+        &mut self,
+        count: usize,
+        penalty: MinePenalty,
+    ) {
+        self.rules.mines = count;
+        self.rules.mine_penalty = Some(penalty);
+    }
+
+    /// Seeds the randomness used during the game, e.g. when a mine hits a random ship cell.
+    /// Without a seed, the game is seeded from the system time.
+    pub fn set_seed(
+        &mut self,
+        seed: u64,
+    ) {
+        self.rules.seed = Some(seed);
+    }
+
+    /// Allows players to spend their turn moving or rotating an undamaged ship instead of
+    /// shooting, see `Game::move_ship`.
+    pub fn set_ship_movement(
+        &mut self,
+        allowed: bool,
+    ) {
+        self.rules.ship_movement = allowed;
+    }
+
+    /// Reveals decoys as `CellStatus::Decoy` to the opponent once all of their cells have been
+    /// hit. By default, hit decoys look just like hit ships.
+    pub fn set_decoy_reveal(
+        &mut self,
+        reveal: bool,
+    ) {
+        self.rules.reveal_decoys = reveal;
+    }
+
+    /// Lets both players shoot at the same time: each round, both players commit a shot and the
+    /// shots are resolved together, see `Game::commit_shot`. If both fleets are destroyed in the
+    /// same round, the game is a draw.
+    pub fn set_simultaneous_turns(
+        &mut self,
+        simultaneous: bool,
+    ) {
+        self.rules.simultaneous = simultaneous;
+    }
+
+    /// Ties the shots per turn to the fleet: each turn, a player may take as many shots as the
+    /// firepower of their surviving ships adds up to, see `ShipType::with_firepower`. The turn
+    /// ends once all shots have been taken, whether they hit or not. Decoys have no firepower.
+    pub fn set_firepower(
+        &mut self,
+        enabled: bool,
+    ) {
+        self.rules.firepower = enabled;
+    }
+
+    /// Sets the topology of both battlefields. Ships placed on a `Toroidal` battlefield may wrap
+    /// around its edges.
+    ///
+    /// # Errors
+    /// * `AlreadyPlaced` if any ships or mines have been placed already.
+    pub fn set_topology(
+        &mut self,
+        topology: Topology,
+    ) -> Result<(), LayoutError> {
+        self.assert_nothing_placed()?;
+        for bf in &mut self.battlefields {
+            bf.set_topology(topology);
+        }
+        Ok(())
+    }
+
+    /// Sets the grid of both battlefields. Hexagonal battlefields use axial coordinates, see
+    /// `Grid` for details.
+    ///
+    /// # Errors
+    /// * `AlreadyPlaced` if any ships or mines have been placed already.
+    pub fn set_grid(
+        &mut self,
+        grid: Grid,
+    ) -> Result<(), LayoutError> {
+        self.assert_nothing_placed()?;
+        for bf in &mut self.battlefields {
+            bf.set_grid(grid);
+        }
+        Ok(())
+    }
+
+    fn assert_nothing_placed(&self) -> Result<(), LayoutError> {
+        if self.placed_ships.is_empty() && self.placed_mines.is_empty() {
+            Ok(())
+        } else {
+            Err(LayoutError::AlreadyPlaced)
         }
     }
 
@@ -89,14 +352,54 @@ impl PreGame {
         } else if length > max(self.width(), self.height()) {
             Err(ShipTooLongForBattlefield)
         } else {
-            let typ = ShipType::new(self.ship_types.len(), name, length);
-            let rc = Arc::new(typ);
+            self.add_custom_ship_type(ShipType::new(0, name, length))
+        }
+    }
+
+    /// Add a ship type with non-default properties, e.g. a submerged layer, to the game. The id
+    /// of `ship_type` is replaced by a unique one.
+    /// # Errors
+    /// * `IllegalShipLength` If the ship type's length is smaller than 1.
+    /// * `ShipTooLongForBattlefield` If the ship length is longer than the width or height of the battlefield.
+    /// * `IllegalArmor` If the ship type's armor is smaller than 1.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lib_battleship::common::Layer;
+    /// # use lib_battleship::common::ShipType;
+    /// # use lib_battleship::PreGame;
+    /// #
+    /// let mut pregame = PreGame::new(3, 3).unwrap();
+    /// let submarine = ShipType::new(0, "Submarine", 2).with_layer(Layer::Submerged);
+    /// let submarine = pregame.add_custom_ship_type(submarine).unwrap();
+    /// ```
+    pub fn add_custom_ship_type(
+        &mut self,
+        ship_type: ShipType,
+    ) -> Result<Arc<ShipType>, ShipTypeError> {
+        if ship_type.length() == 0 {
+            Err(IllegalShipLength)
+        } else if ship_type.length() > max(self.width(), self.height()) {
+            Err(ShipTooLongForBattlefield)
+        } else if ship_type.armor() == 0 {
+            Err(IllegalArmor)
+        } else {
+            let rc = Arc::new(ship_type.with_id(self.ship_types.len()));
             self.ship_types.push(rc.clone());
             Ok(rc)
         }
     }
 
-    /// Place a ship of a previously added ship type on the battlefield.
+    /// Returns the number of depth charges it takes to destroy all submerged ships. Each depth
+    /// charge hits a single cell.
+    fn submerged_health(&self) -> usize {
+        self.ship_types.iter()
+            .filter(|ship_type| ship_type.layer() == Layer::Submerged && !ship_type.is_decoy())
+            .map(|ship_type| ship_type.length() * ship_type.armor())
+            .sum()
+    }
+
+    /// Place a ship of a previously added ship type on the battlefield, on the ship type's layer.
     /// # Parameters
     /// * `player` The player who owns the ship
     /// * `ship_type` Ref to the ship type of the ship to be placed.
@@ -106,9 +409,11 @@ impl PreGame {
     ///
     /// # Errors
     /// * `AlreadyPlaced` In case the player has already placed a ship of that ship type.
-    /// * `OutOfBounds` If the ship would exceed any boundary of the battlefield.
+    /// * `OutOfBounds` If the ship would exceed any boundary of the battlefield. On a toroidal
+    ///   battlefield, if the ship would wrap around onto itself.
     /// * `UnknownShipType` If the ship type id is invalid.
-    /// * `CellOccupied` If the ship would occupy an already occupied coordinate.
+    /// * `CellOccupied` If the ship would occupy an already occupied coordinate on its layer.
+    /// * `IllegalOrientation` If the orientation isn't supported by the grid, see `Grid`.
     ///
     /// # Examples
     /// Player 1 places a corvette of length 2 on (0, 0) and (1, 0)
@@ -133,15 +438,63 @@ impl PreGame {
     ) -> Result<(), PlaceError> {
         self.assert_ship_type_known(&ship_type)?;
         self.assert_ship_not_yet_placed(player, ship_type.id())?;
-        self.assert_ship_placement_in_bounds(&ship_type, x, y, orientation)?;
-        let affected_cell_coords = self.get_affected_cell_coords(&ship_type, x, y, orientation);
-        self.assert_cells_free(player, &affected_cell_coords)?;
+        let position = (x, y, orientation);
+        let bf = self.battlefield_mut(player);
+        if !bf.grid().supports(orientation) {
+            return Err(IllegalOrientation);
+        }
+        let affected_cell_coords = bf.ship_coords(ship_type.length(), position)
+            .ok_or(OutOfBounds)?;
+        if !bf.is_free(ship_type.layer(), &affected_cell_coords, None) {
+            return Err(CellOccupied);
+        }
 
-        self.do_place_ship(player, ship_type.id(), &affected_cell_coords);
+        bf.place_ship(ship_type.id(), ship_type.layer(), position, &affected_cell_coords);
         self.placed_ships.insert((player, ship_type.id()));
         Ok(())
     }
 
+    /// Place a naval mine on the surface of the battlefield.
+    /// # Parameters
+    /// * `player` The player who owns the mine
+    /// * `x` The x coordinate of the mine
+    /// * `y` The y coordinate of the mine
+    ///
+    /// # Errors
+    /// * `NoMinesLeft` In case the player has already placed all their mines, see `set_mines`.
+    /// * `OutOfBounds` If the coordinates are outside the boundaries of the battlefield.
+    /// * `CellOccupied` If the cell already holds a surface ship or a mine.
+    pub fn place_mine(
+        &mut self,
+        player: Player,
+        x: Dimension,
+        y: Dimension,
+    ) -> Result<(), PlaceError> {
+        if self.mines_left(player) == 0 {
+            return Err(NoMinesLeft);
+        }
+        let bf = self.battlefield_mut(player);
+        bf.get_cell(x, y).ok_or(OutOfBounds)?;
+        if !bf.is_free(Layer::Surface, &[(x, y)], None) {
+            return Err(CellOccupied);
+        }
+
+        bf.get_mut_cell(x, y).unwrap().set_mine();
+        self.placed_mines.insert((player, x, y));
+        Ok(())
+    }
+
+    /// Returns how many mines `player` may still place.
+    pub(crate) fn mines_left(
+        &self,
+        player: Player,
+    ) -> usize {
+        let placed = self.placed_mines.iter()
+            .filter(|&&(owner, _, _)| owner == player)
+            .count();
+        self.rules.mines.saturating_sub(placed)
+    }
+
     fn assert_ship_type_known(
         &self,
         ship_type: &Arc<ShipType>,
@@ -166,94 +519,34 @@ impl PreGame {
         }
     }
 
-    fn assert_ship_placement_in_bounds(
-        &self,
-        ship_type: &ShipType,
-        x: Dimension,
-        y: Dimension,
-        orientation: Orientation,
-    ) -> Result<(), PlaceError> {
-        let max_x = match orientation {
-            Orientation::Horizontal => x + ship_type.length() - 1,
-            Orientation::Vertical => x,
-        };
-        let max_y = match orientation {
-            Orientation::Horizontal => y,
-            Orientation::Vertical => y + ship_type.length() - 1,
-        };
-
-        if max_x < self.width && max_y < self.height {
-            Ok(())
-        } else {
-            Err(OutOfBounds)
-        }
-    }
-
-    fn get_affected_cell_coords(
-        &self,
-        ship_type: &ShipType,
-        x: Dimension,
-        y: Dimension,
-        orientation: Orientation,
-    ) -> Vec<(Dimension, Dimension)> {
-        match orientation {
-            Orientation::Horizontal => {
-                (0..ship_type.length())
-                    .map(|n| x + n)
-                    .map(|n| (n, y))
-                    .collect()
-            },
-            Orientation::Vertical => {
-                (0..ship_type.length())
-                    .map(|n| y + n)
-                    .map(|n| (x, n))
-                    .collect()
-            }
-        }
+    pub(crate) fn rules(&self) -> &Rules {
+        &self.rules
     }
 
-    fn assert_cells_free(
+    pub(crate) fn battlefield(
         &self,
         player: Player,
-        cell_coords: &Vec<(Dimension, Dimension)>,
-    ) -> Result<(), PlaceError> {
-        let bf = if player == P1 {
+    ) -> &Battlefield {
+        if player == P1 {
             self.battlefields.get(0).unwrap()
         } else {
             self.battlefields.get(1).unwrap()
-        };
-
-        for coords in cell_coords {
-            let (x, y) = *coords;
-            let cell = bf.get_cell(x, y).unwrap();
-            if cell.ship_type_id().is_some() {
-                return Err(CellOccupied);
-            }
         }
-
-        Ok(())
     }
 
-    fn do_place_ship(
+    fn battlefield_mut(
         &mut self,
         player: Player,
-        ship_type_id: ShipTypeId,
-        affected_cell_coords: &Vec<(Dimension, Dimension)>
-    ) {
-        let bf = if player == P1 {
-            self.battlefields.get_mut(0).unwrap()
+    ) -> &mut Battlefield {
+        if player == P1 {
+            self.battlefields.first_mut().unwrap()
         } else {
             self.battlefields.get_mut(1).unwrap()
-        };
-
-        for coords in affected_cell_coords {
-            let (x, y) = *coords;
-            let cell = bf.get_mut_cell(x, y).unwrap();
-            cell.set_ship_type_id(ship_type_id);
         }
     }
 
-    /// Gets the status of the cell (`x`, `y`) owned by `player`.
+    /// Gets the status of the surface cell (`x`, `y`) owned by `player`, i.e. `Empty`, `Ship`
+    /// or `Mine`.
     /// # Parameters
     /// * `player` determines which battlefield to consider, i.e. the owner of the battlefield.
     /// * `x` the x coordinate
@@ -267,15 +560,26 @@ impl PreGame {
         x: Dimension,
         y: Dimension,
     ) -> CellStatus {
-        let bf = if player == P1 {
-            self.battlefields.get(0).unwrap()
-        } else {
-            self.battlefields.get(1).unwrap()
-        };
-        let cell = bf.get_cell(x, y).unwrap();
+        self.get_layer_cell(player, Layer::Surface, x, y)
+    }
+
+    /// Like `get_cell`, but for the cell (`x`, `y`) on `layer`.
+    ///
+    /// # Panics
+    /// Panics if the x and/or y coordinate is out of bounds.
+    pub fn get_layer_cell(
+        &self,
+        player: Player,
+        layer: Layer,
+        x: Dimension,
+        y: Dimension,
+    ) -> CellStatus {
+        let cell = self.battlefield(player).get_layer_cell(layer, x, y).unwrap();
 
         if let Some(_) = cell.ship_type_id() {
             CellStatus::Ship
+        } else if cell.has_mine() {
+            CellStatus::Mine
         } else {
             CellStatus::Empty
         }
@@ -302,12 +606,18 @@ impl ShipTypeContainer for PreGame {
 mod test {
     use common::CellStatus;
     use common::Dimensional;
+    use common::Grid;
+    use common::Layer::*;
+    use common::MinePenalty;
     use common::Orientation::*;
     use common::Player::*;
     use common::ShipType;
     use common::ShipTypeContainer;
+    use common::Topology;
+    use common::Weapon;
     use pregame::PreGame;
     use results::GameError::IllegalDimensions;
+    use results::LayoutError;
     use results::PlaceError::*;
     use results::GameStartError::*;
     use results::ShipTypeError::*;
@@ -398,6 +708,59 @@ mod test {
         assert_eq!(Ok(()), game.place_ship(P1, &corvette, 1, 0, Horizontal));
     }
 
+    #[test]
+    fn should_wrap_ships_around_toroidal_battlefields() {
+        let mut game = PreGame::new(3, 3).unwrap();
+        game.set_topology(Topology::Toroidal).unwrap();
+        let corvette = game.add_ship_type("Corvette", 2).unwrap();
+        let frigate = game.add_ship_type("Frigate", 2).unwrap();
+
+        assert_eq!(Ok(()), game.place_ship(P1, &corvette, 2, 0, Horizontal));
+        assert_eq!(CellStatus::Ship, game.get_cell(P1, 0, 0));
+        assert_eq!(Err(CellOccupied), game.place_ship(P1, &frigate, 0, 2, Vertical));
+        assert_eq!(Ok(()), game.place_ship(P1, &frigate, 1, 2, Vertical));
+        assert_eq!(CellStatus::Ship, game.get_cell(P1, 1, 0));
+    }
+
+    #[test]
+    fn should_not_change_the_topology_after_placing() {
+        let mut game = PreGame::new(3, 3).unwrap();
+        game.set_mines(1, MinePenalty::LoseNextTurn);
+        game.place_mine(P2, 1, 1).unwrap();
+        assert_eq!(Err(LayoutError::AlreadyPlaced), game.set_topology(Topology::Toroidal));
+
+        let mut game = PreGame::new(3, 3).unwrap();
+        let corvette = game.add_ship_type("Corvette", 2).unwrap();
+        game.place_ship(P1, &corvette, 0, 0, Horizontal).unwrap();
+        assert_eq!(Err(LayoutError::AlreadyPlaced), game.set_topology(Topology::Toroidal));
+        assert_eq!(Err(OutOfBounds), game.place_ship(P2, &corvette, 2, 0, Horizontal));
+    }
+
+    #[test]
+    fn should_not_change_the_grid_after_placing() {
+        let mut game = PreGame::new(3, 3).unwrap();
+        let corvette = game.add_ship_type("Corvette", 2).unwrap();
+        game.place_ship(P1, &corvette, 0, 0, Horizontal).unwrap();
+
+        assert_eq!(Err(LayoutError::AlreadyPlaced), game.set_grid(Grid::Hexagonal));
+        assert_eq!(Err(IllegalOrientation), game.place_ship(P2, &corvette, 0, 1, Diagonal));
+    }
+
+    #[test]
+    fn should_place_ships_along_hex_axes() {
+        let mut game = PreGame::new(3, 3).unwrap();
+        let frigate = game.add_ship_type("Frigate", 3).unwrap();
+        let corvette = game.add_ship_type("Corvette", 2).unwrap();
+
+        assert_eq!(Err(IllegalOrientation), game.place_ship(P1, &frigate, 0, 2, Diagonal));
+        game.set_grid(Grid::Hexagonal).unwrap();
+        assert_eq!(Ok(()), game.place_ship(P1, &frigate, 0, 2, Diagonal));
+        assert_eq!(CellStatus::Ship, game.get_cell(P1, 1, 1));
+        assert_eq!(Err(OutOfBounds), game.place_ship(P1, &corvette, 0, 0, Diagonal));
+        assert_eq!(Err(CellOccupied), game.place_ship(P1, &corvette, 1, 0, Vertical));
+        assert_eq!(Ok(()), game.place_ship(P1, &corvette, 0, 0, Vertical));
+    }
+
     #[test]
     fn should_disallow_placing_ships_on_top_of_each_other() {
         let mut game = PreGame::new(3, 3).unwrap();
@@ -408,6 +771,59 @@ mod test {
         assert_eq!(Err(CellOccupied), game.place_ship(P2, &frigate, 1, 0, Vertical));
     }
 
+    #[test]
+    fn should_place_submerged_ships_below_surface_ships() {
+        let mut game = PreGame::new(3, 3).unwrap();
+        let corvette = game.add_ship_type("Corvette", 2).unwrap();
+        let submarine = game.add_custom_ship_type(ShipType::new(9, "Submarine", 2).with_layer(Submerged)).unwrap();
+        assert_eq!(1, submarine.id());
+        for &player in &[P1, P2] {
+            assert_eq!(Ok(()), game.place_ship(player, &corvette, 0, 0, Horizontal));
+            assert_eq!(Ok(()), game.place_ship(player, &submarine, 0, 0, Vertical));
+        }
+        assert_eq!(CellStatus::Empty, game.get_cell(P1, 0, 1));
+        assert_eq!(CellStatus::Ship, game.get_layer_cell(P1, Submerged, 0, 1));
+
+        let (mut game, err) = game.start().unwrap_err();
+        assert_eq!(SubmergedShipsUnreachable, err);
+        game.set_weapon_uses(Weapon::DepthCharge, 1);
+        let (mut game, err) = game.start().unwrap_err();
+        assert_eq!(SubmergedShipsUnreachable, err);
+        game.set_weapon_uses(Weapon::DepthCharge, 2);
+        assert!(game.start().is_ok());
+    }
+
+    #[test]
+    fn should_not_start_with_only_decoys() {
+        let mut game = PreGame::new(3, 3).unwrap();
+        let decoy = game.add_custom_ship_type(ShipType::new(0, "Decoy", 2).with_decoy(true)).unwrap();
+        game.place_ship(P1, &decoy, 0, 0, Horizontal).unwrap();
+        game.place_ship(P2, &decoy, 0, 0, Horizontal).unwrap();
+
+        assert_eq!(OnlyDecoys, game.start().unwrap_err().1);
+    }
+
+    #[test]
+    fn should_not_start_without_firepower() {
+        let mut game = PreGame::new(3, 3).unwrap();
+        let tanker = game.add_custom_ship_type(ShipType::new(0, "Tanker", 2).with_firepower(0)).unwrap();
+        game.place_ship(P1, &tanker, 0, 0, Horizontal).unwrap();
+        game.place_ship(P2, &tanker, 0, 0, Horizontal).unwrap();
+        game.set_firepower(true);
+
+        let (mut game, err) = game.start().unwrap_err();
+        assert_eq!(NoFirepower, err);
+        game.set_firepower(false);
+        assert!(game.start().is_ok());
+    }
+
+    #[test]
+    fn should_reject_unarmored_ship_types() {
+        let mut game = PreGame::new(3, 3).unwrap();
+        let paper_boat = ShipType::new(0, "Paper boat", 2).with_armor(0);
+        assert_eq!(Err(IllegalArmor), game.add_custom_ship_type(paper_boat));
+    }
+
     #[test]
     fn should_not_start_when_no_ships_placed() {
         let mut game = PreGame::new(2, 2).unwrap();
@@ -451,6 +867,40 @@ mod test {
         }
     }
 
+    #[test]
+    fn should_allow_placing_mines() {
+        let mut game = PreGame::new(3, 3).unwrap();
+        let corvette = game.add_ship_type("Corvette", 2).unwrap();
+        game.set_mines(2, MinePenalty::LoseNextTurn);
+
+        assert_eq!(Ok(()), game.place_mine(P1, 2, 2));
+        assert_eq!(CellStatus::Mine, game.get_cell(P1, 2, 2));
+        assert_eq!(Err(CellOccupied), game.place_mine(P1, 2, 2));
+        assert_eq!(Err(OutOfBounds), game.place_mine(P1, 3, 0));
+        assert_eq!(Ok(()), game.place_mine(P1, 0, 0));
+        assert_eq!(Err(NoMinesLeft), game.place_mine(P1, 1, 1));
+        assert_eq!(Err(CellOccupied), game.place_ship(P1, &corvette, 0, 0, Horizontal));
+        assert_eq!(Ok(()), game.place_ship(P2, &corvette, 0, 0, Horizontal));
+        assert_eq!(Err(CellOccupied), game.place_mine(P2, 1, 0));
+    }
+
+    #[test]
+    fn should_not_start_when_not_all_mines_placed() {
+        let mut game = PreGame::new(2, 2).unwrap();
+        let submarine = game.add_ship_type("Submarine", 1).unwrap();
+        game.set_mines(1, MinePenalty::HitOwnShip);
+        game.place_ship(P1, &submarine, 0, 0, Horizontal).unwrap();
+        game.place_ship(P2, &submarine, 0, 0, Horizontal).unwrap();
+        game.place_mine(P1, 1, 1).unwrap();
+
+        let mut game = match game.start() {
+            Err((game, NotAllMinesPlaced)) => game,
+            _ => unreachable!(),
+        };
+        game.place_mine(P2, 1, 1).unwrap();
+        assert!(game.start().is_ok());
+    }
+
     #[test]
     fn can_get_cell_status() {
         let mut game = PreGame::new(2, 2).unwrap();
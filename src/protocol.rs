@@ -0,0 +1,924 @@
+//! A line protocol to plug bots written in any language into the engine, similar to UCI for
+//! chess engines. The engine runs the bot as a child process and talks to it over its standard
+//! input and output, one message per line, words separated by spaces. Coordinates are 0-based,
+//! orientations are `h`, `v` or `d` (diagonal). On hexagonal battlefields, coordinates are
+//! axial, `x` and `y` being the q and r axes, see `common::Grid`.
+//!
+//! Messages from the engine to the bot:
+//!
+//! * `protocol 1`: the handshake, the bot must answer `ready <name>`
+//! * `newgame <width> <height>`: a new game starts on a battlefield of the given size
+//! * `topology <rectangular|toroidal>` and `grid <square|hexagonal>`: the shape of the
+//!   battlefield, right after `newgame`
+//! * `shotlimit <shots>`: each player may only fire that many shots, only sent if limited
+//! * `weapon <bomb|torpedo|crossstrike|depthcharge> <uses>`: how often each player may use a
+//!   special weapon, only sent for weapons that may be used
+//! * `mines <count> <hitownship|loseturn>`: each player places that many mines, and shooting
+//!   one either hits a random cell of the shooter's own fleet or costs them their next turn;
+//!   only sent if there are mines
+//! * `time <turnlimit> <timebank> <increment>`: the time controls in milliseconds, `-` for no
+//!   turn limit or time bank; a player exceeding the turn limit forfeits the turn, a player
+//!   running out of their time bank loses; only sent if the game is timed
+//! * `ship <index> <length> <surface|submerged> <armor> <ship|decoy>`: one line per ship of the
+//!   fleet, after the rules above; armor is the number of hits it takes to damage a segment
+//! * `firepower <index> <shots>`: the shots the ship adds to each turn while afloat, one line
+//!   per ship after the `ship` lines, only sent if the firepower rule is on; each turn, the bot
+//!   then gets a `move` for every shot of its surviving fleet, whether the shots hit or not
+//! * `place <index>`: the bot must answer `place <x> <y> <orientation>` for the ship
+//! * `placemine`: the bot must answer `mine <x> <y>` for one of its mines, after its ships
+//! * `move`: it's the bot's turn, it must answer `shoot <x> <y>`
+//! * `result <x> <y> <result>`: the result of the bot's last shot, one of `miss`, `hit`,
+//!   `destroyed`, `win`, `mine` or `armored`
+//! * `opponent <x> <y> <result>`: the opponent shot at the bot's battlefield
+//! * `gameover <win|loss|draw>`: the game is over
+//! * `quit`: the bot should exit
+//!
+//! Bots may send `info <text>` lines at any time, e.g. for debugging; the engine ignores them.
+//! Bots should ignore messages they don't know, so the protocol can grow. Bots that don't
+//! answer within the timeout, see `ExternalBot::set_timeout`, are considered lost, and bots
+//! that don't exit shortly after `quit` are killed.
+//!
+//! A minimal session, `>` marking the engine's messages and `<` the bot's:
+//!
+//! ```text
+//! > protocol 1
+//! < ready corner bot
+//! > newgame 3 3
+//! > topology rectangular
+//! > grid square
+//! > ship 0 2 surface 1 ship
+//! > place 0
+//! < place 0 0 h
+//! > move
+//! < shoot 1 1
+//! > result 1 1 miss
+//! > gameover loss
+//! > quit
+//! ```
+
+use ai::Shooter;
+use ai::View;
+use common::Dimensional;
+use common::Grid;
+use common::Layer;
+use common::MinePenalty;
+use common::Orientation;
+use common::Player;
+use common::Player::P1;
+use common::ShipTypeContainer;
+use common::Topology;
+use common::Weapon;
+use pregame::PreGame;
+use results::ProtocolError;
+use results::ProtocolError::*;
+use results::ShootOk;
+use results::Verdict;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read;
+use std::io::Write;
+use std::io::sink;
+use std::mem;
+use std::process::Child;
+use std::process::Command;
+use std::process::Stdio;
+use std::sync::mpsc;
+use std::sync::mpsc::Receiver;
+use std::sync::mpsc::RecvTimeoutError;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+use super::Dimension;
+
+/// The version of the protocol this engine speaks.
+pub const VERSION: u32 = 1;
+
+/// How long the engine waits for a bot's answer by default.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long a bot may take to exit after `quit` before it is killed.
+const GRACE_PERIOD: Duration = Duration::from_secs(1);
+
+/// Messages sent by the engine to the bot.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum EngineMessage {
+    Protocol(u32),
+    NewGame(Dimension, Dimension),
+    Topology(Topology),
+    Grid(Grid),
+    ShotLimit(usize),
+    /// A special weapon and the number of uses per player.
+    Weapon(Weapon, usize),
+    /// The number of mines per player and the penalty for shooting one.
+    Mines(usize, MinePenalty),
+    /// The turn limit, the time bank and the increment.
+    Time(Option<Duration>, Option<Duration>, Duration),
+    /// The index, length, layer and armor of a ship and whether it's a decoy.
+    Ship(usize, Dimension, Layer, usize, bool),
+    /// The index of a ship and its firepower.
+    Firepower(usize, usize),
+    Place(usize),
+    PlaceMine,
+    Move,
+    Result(Dimension, Dimension, ShootOk),
+    Opponent(Dimension, Dimension, ShootOk),
+    GameOver(Verdict),
+    Quit,
+}
+
+impl EngineMessage {
+    /// Formats the message as a line of the protocol, without the line break.
+    pub fn to_line(&self) -> String {
+        match *self {
+            EngineMessage::Protocol(version) => format!("protocol {}", version),
+            EngineMessage::NewGame(width, height) => format!("newgame {} {}", width, height),
+            EngineMessage::Topology(topology) => format!("topology {}", match topology {
+                Topology::Rectangular => "rectangular",
+                Topology::Toroidal => "toroidal",
+            }),
+            EngineMessage::Grid(grid) => format!("grid {}", match grid {
+                Grid::Square => "square",
+                Grid::Hexagonal => "hexagonal",
+            }),
+            EngineMessage::ShotLimit(shots) => format!("shotlimit {}", shots),
+            EngineMessage::Weapon(weapon, uses) => format!("weapon {} {}", weapon_word(weapon), uses),
+            EngineMessage::Mines(count, penalty) => format!("mines {} {}", count, match penalty {
+                MinePenalty::HitOwnShip => "hitownship",
+                MinePenalty::LoseNextTurn => "loseturn",
+            }),
+            EngineMessage::Time(turn_limit, time_bank, increment) => format!(
+                "time {} {} {}",
+                turn_limit.map_or("-".to_string(), |limit| millis(limit).to_string()),
+                time_bank.map_or("-".to_string(), |bank| millis(bank).to_string()),
+                millis(increment),
+            ),
+            EngineMessage::Ship(index, length, layer, armor, decoy) => format!(
+                "ship {} {} {} {} {}",
+                index,
+                length,
+                match layer {
+                    Layer::Surface => "surface",
+                    Layer::Submerged => "submerged",
+                },
+                armor,
+                if decoy { "decoy" } else { "ship" },
+            ),
+            EngineMessage::Firepower(index, shots) => format!("firepower {} {}", index, shots),
+            EngineMessage::Place(index) => format!("place {}", index),
+            EngineMessage::PlaceMine => "placemine".to_string(),
+            EngineMessage::Move => "move".to_string(),
+            EngineMessage::Result(x, y, result) => format!("result {} {} {}", x, y, result_word(result)),
+            EngineMessage::Opponent(x, y, result) => format!("opponent {} {} {}", x, y, result_word(result)),
+            EngineMessage::GameOver(verdict) => format!("gameover {}", match verdict {
+                Verdict::Win => "win",
+                Verdict::Loss => "loss",
+                Verdict::Draw => "draw",
+            }),
+            EngineMessage::Quit => "quit".to_string(),
+        }
+    }
+
+    /// Parses a line of the protocol, returning `None` if it isn't a known engine message.
+    pub fn from_line(line: &str) -> Option<EngineMessage> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let number = |index: usize| words.get(index).and_then(|word| word.parse::<usize>().ok());
+        let result = || words.get(3).and_then(|word| parse_result(word));
+        let duration = |index: usize| words.get(index).and_then(|word| match *word {
+            "-" => Some(None),
+            word => word.parse::<u64>().ok().map(|millis| Some(Duration::from_millis(millis))),
+        });
+        let message = match *words.first()? {
+            "protocol" => EngineMessage::Protocol(number(1)? as u32),
+            "newgame" => EngineMessage::NewGame(number(1)?, number(2)?),
+            "topology" => EngineMessage::Topology(match *words.get(1)? {
+                "rectangular" => Topology::Rectangular,
+                "toroidal" => Topology::Toroidal,
+                _ => return None,
+            }),
+            "grid" => EngineMessage::Grid(match *words.get(1)? {
+                "square" => Grid::Square,
+                "hexagonal" => Grid::Hexagonal,
+                _ => return None,
+            }),
+            "shotlimit" => EngineMessage::ShotLimit(number(1)?),
+            "weapon" => {
+                let word = *words.get(1)?;
+                let weapon = Weapon::ALL.iter().cloned().find(|&weapon| weapon_word(weapon) == word)?;
+                EngineMessage::Weapon(weapon, number(2)?)
+            },
+            "mines" => EngineMessage::Mines(number(1)?, match *words.get(2)? {
+                "hitownship" => MinePenalty::HitOwnShip,
+                "loseturn" => MinePenalty::LoseNextTurn,
+                _ => return None,
+            }),
+            "time" => EngineMessage::Time(duration(1)?, duration(2)?, duration(3)??),
+            "ship" => EngineMessage::Ship(
+                number(1)?,
+                number(2)?,
+                match *words.get(3)? {
+                    "surface" => Layer::Surface,
+                    "submerged" => Layer::Submerged,
+                    _ => return None,
+                },
+                number(4)?,
+                match *words.get(5)? {
+                    "ship" => false,
+                    "decoy" => true,
+                    _ => return None,
+                },
+            ),
+            "firepower" => EngineMessage::Firepower(number(1)?, number(2)?),
+            "place" => EngineMessage::Place(number(1)?),
+            "placemine" => EngineMessage::PlaceMine,
+            "move" => EngineMessage::Move,
+            "result" => EngineMessage::Result(number(1)?, number(2)?, result()?),
+            "opponent" => EngineMessage::Opponent(number(1)?, number(2)?, result()?),
+            "gameover" => EngineMessage::GameOver(match *words.get(1)? {
+                "win" => Verdict::Win,
+                "loss" => Verdict::Loss,
+                "draw" => Verdict::Draw,
+                _ => return None,
+            }),
+            "quit" => EngineMessage::Quit,
+            _ => return None,
+        };
+        Some(message)
+    }
+}
+
+/// Messages sent by the bot to the engine.
+#[derive(Clone, PartialEq, Debug)]
+pub enum BotMessage {
+    /// The bot's name.
+    Ready(String),
+    Place(Dimension, Dimension, Orientation),
+    Mine(Dimension, Dimension),
+    Shoot(Dimension, Dimension),
+    Info(String),
+}
+
+impl BotMessage {
+    /// Formats the message as a line of the protocol, without the line break.
+    pub fn to_line(&self) -> String {
+        match *self {
+            BotMessage::Ready(ref name) => format!("ready {}", name),
+            BotMessage::Place(x, y, orientation) => format!("place {} {} {}", x, y, match orientation {
+                Orientation::Horizontal => "h",
+                Orientation::Vertical => "v",
+                Orientation::Diagonal => "d",
+            }),
+            BotMessage::Mine(x, y) => format!("mine {} {}", x, y),
+            BotMessage::Shoot(x, y) => format!("shoot {} {}", x, y),
+            BotMessage::Info(ref text) => format!("info {}", text),
+        }
+    }
+
+    /// Parses a line of the protocol, returning `None` if it isn't a known bot message.
+    pub fn from_line(line: &str) -> Option<BotMessage> {
+        let line = line.trim();
+        let (keyword, rest) = match line.find(' ') {
+            Some(index) => (&line[..index], line[index..].trim()),
+            None => (line, ""),
+        };
+        let words: Vec<&str> = rest.split_whitespace().collect();
+        let number = |index: usize| words.get(index).and_then(|word| word.parse::<Dimension>().ok());
+        let message = match keyword {
+            "ready" => BotMessage::Ready(rest.to_string()),
+            "place" => BotMessage::Place(number(0)?, number(1)?, match *words.get(2)? {
+                "h" => Orientation::Horizontal,
+                "v" => Orientation::Vertical,
+                "d" => Orientation::Diagonal,
+                _ => return None,
+            }),
+            "mine" => BotMessage::Mine(number(0)?, number(1)?),
+            "shoot" => BotMessage::Shoot(number(0)?, number(1)?),
+            "info" => BotMessage::Info(rest.to_string()),
+            _ => return None,
+        };
+        Some(message)
+    }
+}
+
+fn weapon_word(weapon: Weapon) -> &'static str {
+    match weapon {
+        Weapon::Bomb => "bomb",
+        Weapon::Torpedo => "torpedo",
+        Weapon::CrossStrike => "crossstrike",
+        Weapon::DepthCharge => "depthcharge",
+    }
+}
+
+fn millis(duration: Duration) -> u64 {
+    duration.as_secs() * 1000 + u64::from(duration.subsec_millis())
+}
+
+fn result_word(result: ShootOk) -> &'static str {
+    match result {
+        ShootOk::Miss => "miss",
+        ShootOk::Hit => "hit",
+        ShootOk::Destroyed => "destroyed",
+        ShootOk::WinningShot => "win",
+        ShootOk::Mine => "mine",
+        ShootOk::Armored => "armored",
+    }
+}
+
+fn parse_result(word: &str) -> Option<ShootOk> {
+    match word {
+        "miss" => Some(ShootOk::Miss),
+        "hit" => Some(ShootOk::Hit),
+        "destroyed" => Some(ShootOk::Destroyed),
+        "win" => Some(ShootOk::WinningShot),
+        "mine" => Some(ShootOk::Mine),
+        "armored" => Some(ShootOk::Armored),
+        _ => None,
+    }
+}
+
+/// A bot speaking the protocol, usually an external executable. Use it like any other
+/// `Shooter`, e.g. with `tournament::play_game`, which also forwards the opponent's shots and
+/// the end of the game; as the `Shooter` functions can't fail, protocol errors make
+/// `next_shot` return `None` and are kept for `error`.
+///
+/// ```no_run
+/// # use lib_battleship::PreGame;
+/// # use lib_battleship::ai::{PlacementStrategy, RandomBot};
+/// # use lib_battleship::common::Player::{P1, P2};
+/// use lib_battleship::protocol::ExternalBot;
+/// use lib_battleship::tournament::play_game;
+///
+/// # let mut pregame = PreGame::new(10, 10).unwrap();
+/// # pregame.add_ship_type("Cruiser", 3).unwrap();
+/// let mut bot = ExternalBot::spawn("python3", &["my_bot.py"]).unwrap();
+/// bot.start_game(&pregame).unwrap();
+/// bot.place_fleet(&mut pregame, P1).unwrap();
+/// for placement in PlacementStrategy::Random.place(&pregame, P2, 7).unwrap() {
+///     placement.apply(&mut pregame, P2).unwrap();
+/// }
+///
+/// let mut game = pregame.start().unwrap();
+/// play_game(&mut game, [&mut bot, &mut RandomBot::new(7)]).unwrap();
+/// println!("{:?}", game.get_winner());
+/// ```
+pub struct ExternalBot {
+    name: String,
+    /// The bot's lines, read by a separate thread so reading can time out; `None` once the
+    /// bot closed its output.
+    input: Receiver<Option<String>>,
+    timeout: Option<Duration>,
+    output: Box<dyn Write>,
+    child: Option<Child>,
+    error: Option<ProtocolError>,
+}
+
+impl ExternalBot {
+    /// Runs `program` with `args` and performs the handshake.
+    /// # Errors
+    /// * `SpawnFailed` if the program couldn't be run
+    /// * `Disconnected` if the program exited or closed its output
+    /// * `Timeout` if the program didn't answer the handshake in time
+    /// * `IllegalMessage` if the program didn't answer the handshake properly
+    pub fn spawn(
+        program: &str,
+        args: &[&str],
+    ) -> Result<Self, ProtocolError> {
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .map_err(|_| SpawnFailed)?;
+        let input = child.stdout.take().ok_or(SpawnFailed)?;
+        let output = child.stdin.take().ok_or(SpawnFailed)?;
+        let mut bot = ExternalBot::connect(input, output)?;
+        bot.child = Some(child);
+        Ok(bot)
+    }
+
+    /// Performs the handshake with a bot reading the engine's messages from `output` and
+    /// writing its own to `input`, e.g. a bot in another thread or behind a socket. `input` is
+    /// read by a separate thread, which ends once `input` is closed.
+    /// # Errors
+    /// * `Disconnected` if a stream is closed
+    /// * `Timeout` if the bot didn't answer the handshake in time
+    /// * `IllegalMessage` if the bot didn't answer the handshake properly
+    pub fn connect<R, W>(
+        input: R,
+        output: W,
+    ) -> Result<Self, ProtocolError>
+        where R: Read + Send + 'static,
+              W: Write + 'static
+    {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(input).lines() {
+                if sender.send(line.ok()).is_err() {
+                    return;
+                }
+            }
+            let _ = sender.send(None);
+        });
+        let mut bot = ExternalBot {
+            name: String::new(),
+            input: receiver,
+            timeout: Some(DEFAULT_TIMEOUT),
+            output: Box::new(output),
+            child: None,
+            error: None,
+        };
+        bot.send(EngineMessage::Protocol(VERSION))?;
+        match bot.receive()? {
+            BotMessage::Ready(name) => bot.name = name,
+            _ => return Err(IllegalMessage),
+        }
+        Ok(bot)
+    }
+
+    /// Returns the name the bot announced in the handshake.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Sets how long to wait for each of the bot's answers, or `None` to wait forever. The
+    /// default is `DEFAULT_TIMEOUT`.
+    pub fn set_timeout(
+        &mut self,
+        timeout: Option<Duration>,
+    ) {
+        self.timeout = timeout;
+    }
+
+    /// Returns the error that made one of the `Shooter` functions fail, if any.
+    pub fn error(&self) -> Option<ProtocolError> {
+        self.error
+    }
+
+    /// Announces a new game: the size and shape of the battlefield, the limits on shots and
+    /// weapons, the mines, the time controls and the fleet with its firepower.
+    /// # Errors
+    /// * `Disconnected` if the bot went away
+    pub fn start_game(
+        &mut self,
+        pregame: &PreGame,
+    ) -> Result<(), ProtocolError> {
+        let bf = pregame.battlefield(P1);
+        let rules = pregame.rules();
+        self.send(EngineMessage::NewGame(pregame.width(), pregame.height()))?;
+        self.send(EngineMessage::Topology(bf.topology()))?;
+        self.send(EngineMessage::Grid(bf.grid()))?;
+        if let Some(shots) = rules.shot_limit {
+            self.send(EngineMessage::ShotLimit(shots))?;
+        }
+        for weapon in &Weapon::ALL {
+            if let Some(&uses) = rules.weapon_uses.get(weapon) {
+                self.send(EngineMessage::Weapon(*weapon, uses))?;
+            }
+        }
+        if let Some(penalty) = rules.mine_penalty.filter(|_| rules.mines > 0) {
+            self.send(EngineMessage::Mines(rules.mines, penalty))?;
+        }
+        if let Some(ref clock) = rules.turn_clock {
+            let control = clock.control();
+            self.send(EngineMessage::Time(
+                control.turn_limit(),
+                control.time_bank(),
+                control.increment(),
+            ))?;
+        }
+        for (index, ship_type) in pregame.ship_types().iter().enumerate() {
+            self.send(EngineMessage::Ship(
+                index,
+                ship_type.length(),
+                ship_type.layer(),
+                ship_type.armor(),
+                ship_type.is_decoy(),
+            ))?;
+        }
+        if rules.firepower {
+            for (index, ship_type) in pregame.ship_types().iter().enumerate() {
+                self.send(EngineMessage::Firepower(index, ship_type.firepower()))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Asks the bot where to put every ship and mine `player` hasn't placed yet and places them.
+    /// # Errors
+    /// * `Disconnected` if the bot went away
+    /// * `Timeout` if the bot didn't answer in time
+    /// * `IllegalMessage` if the bot didn't answer with a placement
+    /// * `IllegalPlacement` if `PreGame::place_ship` or `PreGame::place_mine` rejected a
+    ///   placement
+    pub fn place_fleet(
+        &mut self,
+        pregame: &mut PreGame,
+        player: Player,
+    ) -> Result<(), ProtocolError> {
+        for (index, ship_type) in pregame.ship_types().iter().enumerate() {
+            if pregame.battlefield(player).ship_position(ship_type.id()).is_some() {
+                continue;
+            }
+            self.send(EngineMessage::Place(index))?;
+            match self.receive()? {
+                BotMessage::Place(x, y, orientation) => pregame
+                    .place_ship(player, ship_type, x, y, orientation)
+                    .map_err(|_| IllegalPlacement)?,
+                _ => return Err(IllegalMessage),
+            }
+        }
+        while pregame.mines_left(player) > 0 {
+            self.send(EngineMessage::PlaceMine)?;
+            match self.receive()? {
+                BotMessage::Mine(x, y) => pregame
+                    .place_mine(player, x, y)
+                    .map_err(|_| IllegalPlacement)?,
+                _ => return Err(IllegalMessage),
+            }
+        }
+        Ok(())
+    }
+
+    fn send(
+        &mut self,
+        message: EngineMessage,
+    ) -> Result<(), ProtocolError> {
+        writeln!(self.output, "{}", message.to_line())
+            .and_then(|_| self.output.flush())
+            .map_err(|_| Disconnected)
+    }
+
+    /// Reads the next message, skipping `info` lines and lines the engine doesn't know. The
+    /// timeout applies to every line.
+    fn receive(&mut self) -> Result<BotMessage, ProtocolError> {
+        loop {
+            let line = match self.timeout {
+                Some(timeout) => self.input.recv_timeout(timeout).map_err(|err| match err {
+                    RecvTimeoutError::Timeout => Timeout,
+                    RecvTimeoutError::Disconnected => Disconnected,
+                })?,
+                None => self.input.recv().map_err(|_| Disconnected)?,
+            };
+            match BotMessage::from_line(&line.ok_or(Disconnected)?) {
+                Some(BotMessage::Info(_)) | None => continue,
+                Some(message) => return Ok(message),
+            }
+        }
+    }
+}
+
+impl Shooter for ExternalBot {
+    fn next_shot(
+        &mut self,
+        _view: &View,
+    ) -> Option<(Dimension, Dimension)> {
+        if self.error.is_some() {
+            return None;
+        }
+        let shot = self.send(EngineMessage::Move)
+            .and_then(|_| self.receive())
+            .and_then(|message| match message {
+                BotMessage::Shoot(x, y) => Ok((x, y)),
+                _ => Err(IllegalMessage),
+            });
+        match shot {
+            Ok(shot) => Some(shot),
+            Err(err) => {
+                self.error = Some(err);
+                None
+            },
+        }
+    }
+
+    fn record_result(
+        &mut self,
+        x: Dimension,
+        y: Dimension,
+        result: ShootOk,
+    ) {
+        if let Err(err) = self.send(EngineMessage::Result(x, y, result)) {
+            self.error = Some(err);
+        }
+    }
+
+    fn record_opponent_shot(
+        &mut self,
+        x: Dimension,
+        y: Dimension,
+        result: ShootOk,
+    ) {
+        if let Err(err) = self.send(EngineMessage::Opponent(x, y, result)) {
+            self.error = Some(err);
+        }
+    }
+
+    fn game_over(
+        &mut self,
+        verdict: Verdict,
+    ) {
+        if let Err(err) = self.send(EngineMessage::GameOver(verdict)) {
+            self.error = Some(err);
+        }
+    }
+}
+
+impl Drop for ExternalBot {
+    fn drop(&mut self) {
+        let _ = self.send(EngineMessage::Quit);
+        // closing the bot's input lets it exit even if it doesn't understand `quit`
+        drop(mem::replace(&mut self.output, Box::new(sink())));
+        if let Some(mut child) = self.child.take() {
+            let start = Instant::now();
+            while let Ok(None) = child.try_wait() {
+                if start.elapsed() >= GRACE_PERIOD {
+                    let _ = child.kill();
+                    break;
+                }
+                thread::sleep(Duration::from_millis(10));
+            }
+            let _ = child.wait();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ai::Shooter;
+    use ai::View;
+    use common::CellStatus;
+    use common::Grid;
+    use common::Layer;
+    use common::MinePenalty;
+    use common::Orientation::*;
+    use common::Player::*;
+    use common::ShipType;
+    use common::Topology;
+    use common::Weapon;
+    use pregame::PreGame;
+    use results::ProtocolError::*;
+    use results::ShootOk;
+    use results::Verdict;
+    use std::io;
+    use std::io::Cursor;
+    use std::io::Read;
+    use std::io::Write;
+    use std::io::sink;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+    use std::thread;
+    use std::time::Duration;
+    use std::time::Instant;
+    use super::BotMessage;
+    use super::EngineMessage;
+    use super::ExternalBot;
+    use time::ManualClock;
+    use time::TimeControl;
+    use tournament::play_game;
+    use ::Dimension;
+
+    #[test]
+    fn should_format_and_parse_messages() {
+        let engine = [
+            EngineMessage::Protocol(1),
+            EngineMessage::NewGame(10, 8),
+            EngineMessage::Topology(Topology::Toroidal),
+            EngineMessage::Grid(Grid::Hexagonal),
+            EngineMessage::ShotLimit(30),
+            EngineMessage::Weapon(Weapon::DepthCharge, 2),
+            EngineMessage::Mines(3, MinePenalty::LoseNextTurn),
+            EngineMessage::Time(Some(Duration::from_millis(1500)), None, Duration::from_secs(2)),
+            EngineMessage::Ship(0, 5, Layer::Surface, 1, false),
+            EngineMessage::Ship(1, 2, Layer::Submerged, 2, true),
+            EngineMessage::Firepower(1, 3),
+            EngineMessage::Place(0),
+            EngineMessage::PlaceMine,
+            EngineMessage::Move,
+            EngineMessage::Result(3, 4, ShootOk::Destroyed),
+            EngineMessage::Opponent(0, 9, ShootOk::Armored),
+            EngineMessage::GameOver(Verdict::Draw),
+            EngineMessage::Quit,
+        ];
+        for message in &engine {
+            assert_eq!(Some(*message), EngineMessage::from_line(&message.to_line()));
+        }
+
+        let bot = [
+            BotMessage::Ready("deep sea".to_string()),
+            BotMessage::Place(1, 2, Vertical),
+            BotMessage::Mine(4, 4),
+            BotMessage::Shoot(7, 0),
+            BotMessage::Info("thinking".to_string()),
+        ];
+        for message in &bot {
+            assert_eq!(Some(message.clone()), BotMessage::from_line(&message.to_line()));
+        }
+        assert_eq!(Some(BotMessage::Shoot(1, 2)), BotMessage::from_line("  shoot 1 2\n"));
+        assert_eq!(None, BotMessage::from_line("shoot 1"));
+        assert_eq!(None, BotMessage::from_line("place 1 2 x"));
+        assert_eq!(None, EngineMessage::from_line("result 1 2 sunk"));
+        assert_eq!(None, EngineMessage::from_line("weapon nuke 1"));
+        assert_eq!(None, EngineMessage::from_line("time - - -"));
+    }
+
+    /// Collects the engine's messages.
+    #[derive(Clone, Default)]
+    struct Transcript(Arc<Mutex<Vec<u8>>>);
+
+    impl Transcript {
+        fn lines(&self) -> Vec<String> {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+                .lines()
+                .map(|line| line.to_string())
+                .collect()
+        }
+    }
+
+    impl Write for Transcript {
+        fn write(
+            &mut self,
+            buf: &[u8],
+        ) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// A bot that never says anything.
+    struct Silent;
+
+    impl Read for Silent {
+        fn read(
+            &mut self,
+            _buf: &mut [u8],
+        ) -> io::Result<usize> {
+            loop {
+                thread::park();
+            }
+        }
+    }
+
+    #[test]
+    fn should_announce_the_rules() {
+        let transcript = Transcript::default();
+        let mut bot = ExternalBot::connect(Cursor::new("ready\n"), transcript.clone()).unwrap();
+
+        let mut pregame = PreGame::new(4, 3).unwrap();
+        pregame.set_topology(Topology::Toroidal).unwrap();
+        pregame.set_shot_limit(8);
+        pregame.set_weapon_uses(Weapon::DepthCharge, 2);
+        pregame.set_weapon_uses(Weapon::Bomb, 1);
+        pregame.add_ship_type("Corvette", 2).unwrap();
+        pregame.add_custom_ship_type(ShipType::new(0, "Submarine", 2).with_layer(Layer::Submerged).with_armor(2))
+            .unwrap();
+        pregame.add_custom_ship_type(ShipType::new(0, "Decoy", 1).with_decoy(true)).unwrap();
+        bot.start_game(&pregame).unwrap();
+
+        assert_eq!(vec!(
+            "protocol 1",
+            "newgame 4 3",
+            "topology toroidal",
+            "grid square",
+            "shotlimit 8",
+            "weapon bomb 1",
+            "weapon depthcharge 2",
+            "ship 0 2 surface 1 ship",
+            "ship 1 2 submerged 2 ship",
+            "ship 2 1 surface 1 decoy",
+        ), transcript.lines());
+    }
+
+    #[test]
+    fn should_announce_mines_time_controls_and_firepower() {
+        let transcript = Transcript::default();
+        let mut bot = ExternalBot::connect(Cursor::new("ready\n"), transcript.clone()).unwrap();
+
+        let mut pregame = PreGame::new(4, 3).unwrap();
+        pregame.set_mines(2, MinePenalty::HitOwnShip);
+        let control = TimeControl::new(None, Some(Duration::from_secs(60)), Duration::from_millis(500))
+            .unwrap();
+        pregame.set_time_control(control, Arc::new(ManualClock::new()));
+        pregame.set_firepower(true);
+        pregame.add_ship_type("Corvette", 2).unwrap();
+        pregame.add_custom_ship_type(ShipType::new(0, "Cruiser", 3).with_firepower(2)).unwrap();
+        bot.start_game(&pregame).unwrap();
+
+        assert_eq!(vec!(
+            "protocol 1",
+            "newgame 4 3",
+            "topology rectangular",
+            "grid square",
+            "mines 2 hitownship",
+            "time - 60000 500",
+            "ship 0 2 surface 1 ship",
+            "ship 1 3 surface 1 ship",
+            "firepower 0 1",
+            "firepower 1 2",
+        ), transcript.lines());
+    }
+
+    #[test]
+    fn should_let_bots_place_mines() {
+        let answers = "ready\nplace 0 0 h\nmine 3 0\nmine 3 2\n";
+        let mut bot = ExternalBot::connect(Cursor::new(answers), sink()).unwrap();
+
+        let mut pregame = PreGame::new(4, 3).unwrap();
+        pregame.set_mines(2, MinePenalty::LoseNextTurn);
+        pregame.add_ship_type("Corvette", 2).unwrap();
+        bot.start_game(&pregame).unwrap();
+        bot.place_fleet(&mut pregame, P1).unwrap();
+        assert_eq!(CellStatus::Mine, pregame.get_cell(P1, 3, 0));
+        assert_eq!(CellStatus::Mine, pregame.get_cell(P1, 3, 2));
+    }
+
+    #[test]
+    fn should_drive_a_bot() {
+        let answers = "ready test bot\ninfo placing\nplace 0 0 h\nplace 2 0 v\nshoot 1 1\n";
+        let mut bot = ExternalBot::connect(Cursor::new(answers), sink()).unwrap();
+        assert_eq!("test bot", bot.name());
+
+        let mut pregame = PreGame::new(3, 3).unwrap();
+        pregame.add_ship_type("Corvette", 2).unwrap();
+        pregame.add_ship_type("Frigate", 2).unwrap();
+        bot.start_game(&pregame).unwrap();
+        bot.place_fleet(&mut pregame, P1).unwrap();
+        assert_eq!(CellStatus::Ship, pregame.get_cell(P1, 1, 0));
+        assert_eq!(CellStatus::Ship, pregame.get_cell(P1, 2, 1));
+
+        let view = View::new(3, 3);
+        assert_eq!(Some((1, 1)), bot.next_shot(&view));
+        bot.record_result(1, 1, ShootOk::Miss);
+        assert_eq!(None, bot.error());
+        assert_eq!(None, bot.next_shot(&view));
+        assert_eq!(Some(Disconnected), bot.error());
+    }
+
+    #[test]
+    fn should_give_up_on_silent_bots() {
+        let mut bot = ExternalBot::connect(Cursor::new("ready\n").chain(Silent), sink()).unwrap();
+        bot.set_timeout(Some(Duration::from_millis(50)));
+
+        assert_eq!(None, bot.next_shot(&View::new(3, 3)));
+        assert_eq!(Some(Timeout), bot.error());
+    }
+
+    #[test]
+    fn should_kill_bots_that_dont_quit() {
+        let bot = ExternalBot::spawn("sh", &["-c", "echo ready; exec sleep 30"]).unwrap();
+        let start = Instant::now();
+        drop(bot);
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+
+    /// Shoots at the given cells in order.
+    struct Scripted(Vec<(Dimension, Dimension)>);
+
+    impl Shooter for Scripted {
+        fn next_shot(
+            &mut self,
+            _view: &View,
+        ) -> Option<(Dimension, Dimension)> {
+            if self.0.is_empty() { None } else { Some(self.0.remove(0)) }
+        }
+    }
+
+    #[test]
+    fn should_play_a_game() {
+        let transcript = Transcript::default();
+        let answers = "ready\nplace 0 0 h\nshoot 2 2\nshoot 0 1\nshoot 1 1\n";
+        let mut bot = ExternalBot::connect(Cursor::new(answers), transcript.clone()).unwrap();
+
+        let mut pregame = PreGame::new(3, 3).unwrap();
+        let corvette = pregame.add_ship_type("Corvette", 2).unwrap();
+        bot.start_game(&pregame).unwrap();
+        bot.place_fleet(&mut pregame, P1).unwrap();
+        pregame.place_ship(P2, &corvette, 0, 1, Horizontal).unwrap();
+        let mut game = pregame.start().unwrap();
+
+        let shots = play_game(&mut game, [&mut bot, &mut Scripted(vec!((1, 1)))]).unwrap();
+        assert_eq!([3, 1], shots);
+        assert_eq!(None, bot.error());
+        assert_eq!(vec!(
+            "move",
+            "result 2 2 miss",
+            "opponent 1 1 miss",
+            "move",
+            "result 0 1 hit",
+            "move",
+            "result 1 1 win",
+            "gameover win",
+        ), transcript.lines()[6..].to_vec());
+    }
+
+    #[test]
+    fn should_reject_bad_answers() {
+        assert_eq!(Some(IllegalMessage), ExternalBot::connect(Cursor::new("shoot 1 1\n"), sink()).err());
+        assert_eq!(Some(Disconnected), ExternalBot::connect(Cursor::new(""), sink()).err());
+
+        let mut pregame = PreGame::new(3, 3).unwrap();
+        pregame.add_ship_type("Corvette", 2).unwrap();
+        let mut bot = ExternalBot::connect(Cursor::new("ready\nplace 2 2 h\n"), sink()).unwrap();
+        assert_eq!(Err(IllegalPlacement), bot.place_fleet(&mut pregame, P2));
+    }
+}
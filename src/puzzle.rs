@@ -0,0 +1,269 @@
+//! A single-player game against a fleet placed by the computer.
+
+use common::CellStatus;
+use common::Player::*;
+use common::Weapon;
+use game::Game;
+use results::EndReason;
+use results::Impact;
+use results::PuzzleOutcome;
+use results::ShootError;
+use results::ShootError::GameOver;
+use results::ShootOk;
+use super::Dimension;
+
+/// A single-player game: destroy the computer's fleet with a limited number of shots. The
+/// player is player 1 and never loses their turn. Set one up with `PreGame::start_puzzle`.
+#[derive(PartialEq, Debug)]
+pub struct Puzzle {
+    game: Game,
+    shot_budget: usize,
+    shots_fired: usize,
+    fleet_health: Dimension,
+}
+
+impl Puzzle {
+    pub(crate) fn new(
+        game: Game,
+        shot_budget: usize,
+    ) -> Self {
+        let fleet_health = game.fleet_health(P2);
+        Puzzle {
+            game,
+            shot_budget,
+            shots_fired: 0,
+            fleet_health,
+        }
+    }
+
+    pub(crate) fn game(&self) -> &Game {
+        &self.game
+    }
+
+    /// Shoot at the computer's fleet. Uses up one shot of the budget.
+    /// # Errors
+    /// * `OutOfBounds` if the given coordinates are outside the boundaries of the battlefield.
+    /// * `GameOver` if the puzzle is already finished
+    pub fn shoot(
+        &mut self,
+        x: Dimension,
+        y: Dimension,
+    ) -> Result<ShootOk, ShootError> {
+        self.assert_running()?;
+        let result = self.game.shoot(P2, x, y)?;
+        self.shots_fired += 1;
+        Ok(result)
+    }
+
+    /// Attack the computer's fleet with a special weapon, see `Game::fire`. Uses up one shot of
+    /// the budget, no matter how many cells the weapon hits.
+    /// # Errors
+    /// * `OutOfBounds` if the targeted cell is outside the boundaries of the battlefield.
+    /// * `GameOver` if the puzzle is already finished
+    /// * `WeaponUnavailable` if there are no uses of `weapon` left.
+    pub fn fire(
+        &mut self,
+        weapon: Weapon,
+        x: Dimension,
+        y: Dimension,
+    ) -> Result<Vec<Impact>, ShootError> {
+        self.assert_running()?;
+        let impacts = self.game.fire(P2, weapon, x, y)?;
+        self.shots_fired += 1;
+        Ok(impacts)
+    }
+
+    fn assert_running(&mut self) -> Result<(), ShootError> {
+        self.game.check_time();
+        if self.outcome().is_some() {
+            Err(GameOver)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Returns how many more times `weapon` may be used.
+    pub fn weapon_uses(
+        &self,
+        weapon: Weapon,
+    ) -> usize {
+        self.game.weapon_uses(P1, weapon)
+    }
+
+    /// Returns the number of shots the puzzle started with.
+    pub fn shot_budget(&self) -> usize {
+        self.shot_budget
+    }
+
+    /// Returns the number of shots taken so far.
+    pub fn shots_fired(&self) -> usize {
+        self.shots_fired
+    }
+
+    /// Returns the number of shots left.
+    pub fn shots_left(&self) -> usize {
+        self.shot_budget - self.shots_fired
+    }
+
+    /// Gets the status of the cell (`x`, `y`) as seen by the player, see
+    /// `Game::get_opponent_cell`.
+    ///
+    /// # Panics
+    /// Panics if the x and/or y coordinate is out of bounds.
+    pub fn get_cell(
+        &self,
+        x: Dimension,
+        y: Dimension,
+    ) -> CellStatus {
+        self.game.get_opponent_cell(P2, x, y)
+    }
+
+    /// Gets the outcome of the puzzle, or `None` while it's still running. A puzzle ending for
+    /// any other reason than a destroyed fleet or a timeout, e.g. a shot limit, counts as
+    /// `OutOfShots`.
+    pub fn outcome(&self) -> Option<PuzzleOutcome> {
+        match self.game.outcome() {
+            Some(outcome) => Some(match outcome.reason() {
+                EndReason::FleetDestroyed if outcome.winner() == Some(P1) => PuzzleOutcome::Solved,
+                EndReason::Timeout => PuzzleOutcome::OutOfTime,
+                _ => PuzzleOutcome::OutOfShots,
+            }),
+            None if self.shots_left() == 0 => Some(PuzzleOutcome::OutOfShots),
+            None => None,
+        }
+    }
+
+    /// Gets the score once the puzzle is finished. Each damaged ship segment is worth 10 points.
+    /// Solving the puzzle adds a bonus of 100 points plus 10 points per shot left.
+    pub fn score(&self) -> Option<usize> {
+        let outcome = self.outcome()?;
+        let damaged = self.fleet_health - self.game.fleet_health(P2);
+        let bonus = if outcome == PuzzleOutcome::Solved {
+            100 + 10 * self.shots_left()
+        } else {
+            0
+        };
+        Some(10 * damaged + bonus)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::CellStatus;
+    use common::Layer::Submerged;
+    use common::Orientation::*;
+    use common::Player::*;
+    use common::ShipType;
+    use pregame::PreGame;
+    use results::GameStartError::{OnlyDecoys, SubmergedShipsUnreachable};
+    use results::PuzzleError::*;
+    use results::PuzzleOutcome;
+    use results::ShootError::*;
+    use results::ShootOk::*;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use time::ManualClock;
+    use time::TimeControl;
+
+    fn build_test_pregame() -> PreGame {
+        let mut pregame = PreGame::new(3, 3).unwrap();
+        let corvette = pregame.add_ship_type("Corvette", 2).unwrap();
+        pregame.place_ship(P2, &corvette, 0, 0, Horizontal).unwrap();
+
+        pregame
+    }
+
+    #[test]
+    fn should_validate_puzzle_setup() {
+        let (pregame, err) = PreGame::new(3, 3).unwrap().start_puzzle(5).unwrap_err();
+        assert_eq!(NoShipTypes, err);
+        assert_eq!(IllegalShotBudget, build_test_pregame().start_puzzle(0).unwrap_err().1);
+
+        let mut pregame = pregame;
+        pregame.add_ship_type("Carrier", 3).unwrap();
+        pregame.add_ship_type("Battleship", 3).unwrap();
+        pregame.add_ship_type("Cruiser", 3).unwrap();
+        pregame.add_ship_type("Destroyer", 3).unwrap();
+        assert_eq!(FleetDoesNotFit, pregame.start_puzzle(5).unwrap_err().1);
+    }
+
+    #[test]
+    fn should_check_the_fleet_like_games_do() {
+        let mut pregame = PreGame::new(3, 3).unwrap();
+        pregame.add_custom_ship_type(ShipType::new(0, "Decoy", 2).with_decoy(true)).unwrap();
+        let (mut pregame, err) = pregame.start_puzzle(5).unwrap_err();
+        assert_eq!(IllegalFleet(OnlyDecoys), err);
+
+        pregame.add_custom_ship_type(ShipType::new(0, "Submarine", 2).with_layer(Submerged)).unwrap();
+        assert_eq!(IllegalFleet(SubmergedShipsUnreachable), pregame.start_puzzle(5).unwrap_err().1);
+    }
+
+    #[test]
+    fn should_keep_the_turn_and_score_a_solved_puzzle() {
+        let mut puzzle = build_test_pregame().start_puzzle(4).unwrap();
+
+        assert_eq!(Ok(Miss), puzzle.shoot(2, 2));
+        assert_eq!(Ok(Hit), puzzle.shoot(0, 0));
+        assert_eq!(CellStatus::Hit, puzzle.get_cell(0, 0));
+        assert_eq!(None, puzzle.score());
+        assert_eq!(Ok(WinningShot), puzzle.shoot(1, 0));
+
+        assert_eq!(Some(PuzzleOutcome::Solved), puzzle.outcome());
+        assert_eq!(1, puzzle.shots_left());
+        assert_eq!(Some(130), puzzle.score());
+        assert_eq!(Err(GameOver), puzzle.shoot(2, 1));
+    }
+
+    #[test]
+    fn should_end_when_the_shots_run_out() {
+        let mut puzzle = build_test_pregame().start_puzzle(2).unwrap();
+
+        assert_eq!(Ok(Hit), puzzle.shoot(1, 0));
+        assert_eq!(Ok(Miss), puzzle.shoot(2, 2));
+        assert_eq!(Some(PuzzleOutcome::OutOfShots), puzzle.outcome());
+        assert_eq!(Some(10), puzzle.score());
+        assert_eq!(Err(GameOver), puzzle.shoot(0, 0));
+    }
+
+    #[test]
+    fn should_end_when_the_time_runs_out() {
+        let mut pregame = build_test_pregame();
+        let clock = Arc::new(ManualClock::new());
+        let control = TimeControl::new(None, Some(Duration::from_secs(60)), Duration::from_secs(0)).unwrap();
+        pregame.set_time_control(control, clock.clone());
+        let mut puzzle = pregame.start_puzzle(4).unwrap();
+
+        assert_eq!(Ok(Hit), puzzle.shoot(0, 0));
+        clock.advance(Duration::from_secs(61));
+        assert_eq!(Err(GameOver), puzzle.shoot(1, 0));
+        assert_eq!(Some(PuzzleOutcome::OutOfTime), puzzle.outcome());
+    }
+
+    #[test]
+    fn should_keep_the_turn_when_it_times_out() {
+        let mut pregame = build_test_pregame();
+        let clock = Arc::new(ManualClock::new());
+        let control = TimeControl::new(Some(Duration::from_secs(10)), None, Duration::from_secs(0)).unwrap();
+        pregame.set_time_control(control, clock.clone());
+        let mut puzzle = pregame.start_puzzle(4).unwrap();
+
+        clock.advance(Duration::from_secs(11));
+        assert_eq!(Ok(Hit), puzzle.shoot(0, 0));
+        assert_eq!(None, puzzle.outcome());
+    }
+
+    #[test]
+    fn should_place_the_fleet_randomly() {
+        let mut pregame = PreGame::new(3, 3).unwrap();
+        pregame.add_ship_type("Corvette", 2).unwrap();
+        pregame.add_ship_type("Submarine", 1).unwrap();
+        pregame.set_seed(3);
+        let mut puzzle = pregame.start_puzzle(9).unwrap();
+
+        let hits = (0..3).flat_map(|y| (0..3).map(move |x| (x, y)))
+            .filter(|&(x, y)| puzzle.shoot(x, y).map(|result| result != Miss).unwrap_or(false))
+            .count();
+        assert_eq!(3, hits);
+        assert_eq!(Some(PuzzleOutcome::Solved), puzzle.outcome());
+    }
+}
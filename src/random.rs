@@ -0,0 +1,79 @@
+//! A small, seedable pseudo random number generator.
+
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+/// SplitMix64 generator. Not suitable for cryptography, but fast and reproducible.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng {
+            state: seed,
+        }
+    }
+
+    /// Creates a new instance seeded from the system time.
+    pub fn from_time() -> Self {
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_nanos() as u64)
+            .unwrap_or(0);
+        Rng::new(nanos)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a number in `0..bound`.
+    ///
+    /// # Panics
+    /// Panics if `bound` is zero.
+    pub fn below(
+        &mut self,
+        bound: usize,
+    ) -> usize {
+        assert!(bound > 0, "bound must not be zero");
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    /// Returns `true` with the given probability, between 0 and 1.
+    pub fn chance(
+        &mut self,
+        probability: f64,
+    ) -> bool {
+        probability > 0.0 && ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < probability
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Rng;
+
+    #[test]
+    fn should_be_reproducible() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+
+        for _ in 0..10 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+        assert_ne!(Rng::new(1).next_u64(), Rng::new(2).next_u64());
+    }
+
+    #[test]
+    fn should_stay_within_bounds() {
+        let mut rng = Rng::new(7);
+
+        for _ in 0..100 {
+            assert!(rng.below(3) < 3);
+        }
+    }
+}
@@ -1,5 +1,8 @@
 //! Result types for all operations that can fail.
 
+use common::Player;
+use ::Dimension;
+
 /// General errors when creating a game.
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub enum GameError {
@@ -11,6 +14,7 @@ pub enum GameError {
 pub enum ShipTypeError {
     IllegalShipLength,
     ShipTooLongForBattlefield,
+    IllegalArmor,
 }
 
 /// Errors that can occur whene placing ships.
@@ -20,6 +24,15 @@ pub enum PlaceError {
     OutOfBounds,
     UnknownShipType,
     CellOccupied,
+    NoMinesLeft,
+    IllegalOrientation,
+}
+
+/// Errors that can occur when changing the shape of the battlefields.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum LayoutError {
+    /// Ships or mines have already been placed, and might not fit the new shape.
+    AlreadyPlaced,
 }
 
 /// Possible errors when trying to start playing.
@@ -27,6 +40,124 @@ pub enum PlaceError {
 pub enum GameStartError {
     NoShipsPlaced,
     NotAllShipsPlaced,
+    NotAllMinesPlaced,
+    SubmergedShipsUnreachable,
+    OnlyDecoys,
+    NoFirepower,
+}
+
+/// Possible errors when trying to start a puzzle.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum PuzzleError {
+    NoShipTypes,
+    IllegalShotBudget,
+    FleetDoesNotFit,
+    /// The fleet breaks one of the rules `PreGame::start` checks as well, e.g. `OnlyDecoys`.
+    IllegalFleet(GameStartError),
+}
+
+/// Possible errors when playing a bot-vs-bot match.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum MatchError {
+    FleetDoesNotFit,
+    IllegalSetup,
+    IllegalShot,
+    NoShot,
+}
+
+/// The outcome of a game from a player's point of view.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Verdict {
+    Win,
+    Loss,
+    Draw,
+}
+
+/// Possible errors when talking to a bot over the engine protocol.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ProtocolError {
+    SpawnFailed,
+    Disconnected,
+    Timeout,
+    IllegalMessage,
+    IllegalPlacement,
+}
+
+/// Possible errors of a reinforcement learning environment.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum EnvironmentError {
+    FleetDoesNotFit,
+    NotReset,
+    EpisodeOver,
+    IllegalAction,
+}
+
+/// Why a cell is suggested as the next target, see `Hint`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum HintReason {
+    /// The cell holds an armored ship segment that hasn't been damaged yet.
+    ArmorHeld,
+    /// The cell extends a line of hits.
+    ExtendsLine,
+    /// The cell is next to a hit of a ship that may still be afloat.
+    AdjacentToHit,
+    /// Most placements of the remaining ships cover the cell.
+    HighestDensity,
+}
+
+impl HintReason {
+    /// Returns a short explanation for players.
+    pub fn explanation(&self) -> &'static str {
+        match *self {
+            HintReason::ArmorHeld => "armor held, shoot again",
+            HintReason::ExtendsLine => "extends a line of hits",
+            HintReason::AdjacentToHit => "adjacent to an unsunk hit",
+            HintReason::HighestDensity => "highest placement density",
+        }
+    }
+}
+
+/// A suggested target, see `Game::suggest_shot`.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Hint {
+    x: Dimension,
+    y: Dimension,
+    reason: HintReason,
+}
+
+impl Hint {
+    /// Creates a new instance.
+    pub fn new(
+        x: Dimension,
+        y: Dimension,
+        reason: HintReason,
+    ) -> Self {
+        Hint {
+            x,
+            y,
+            reason,
+        }
+    }
+
+    /// Returns the x coordinate of the suggested cell.
+    pub fn x(&self) -> Dimension {
+        self.x
+    }
+
+    /// Returns the y coordinate of the suggested cell.
+    pub fn y(&self) -> Dimension {
+        self.y
+    }
+
+    /// Returns why the cell is suggested.
+    pub fn reason(&self) -> HintReason {
+        self.reason
+    }
+
+    /// Returns a short explanation for players, see `HintReason::explanation`.
+    pub fn explanation(&self) -> &'static str {
+        self.reason.explanation()
+    }
 }
 
 /// Possible positive outcomes of shooting.
@@ -36,6 +167,47 @@ pub enum ShootOk {
     Miss,
     Destroyed,
     WinningShot,
+    Mine,
+    /// The shot hit an armored ship segment, but didn't damage it yet.
+    Armored,
+}
+
+/// The result of an attack on a single cell.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Impact {
+    x: Dimension,
+    y: Dimension,
+    result: ShootOk,
+}
+
+impl Impact {
+    /// Creates a new instance.
+    pub fn new(
+        x: Dimension,
+        y: Dimension,
+        result: ShootOk,
+    ) -> Self {
+        Impact {
+            x,
+            y,
+            result,
+        }
+    }
+
+    /// Returns the x coordinate of the attacked cell.
+    pub fn x(&self) -> Dimension {
+        self.x
+    }
+
+    /// Returns the y coordinate of the attacked cell.
+    pub fn y(&self) -> Dimension {
+        self.y
+    }
+
+    /// Returns the result of the attack on this cell.
+    pub fn result(&self) -> ShootOk {
+        self.result
+    }
 }
 
 /// Errors that can occur when shooting.
@@ -44,4 +216,207 @@ pub enum ShootError {
     NotThisPlayersTurn,
     OutOfBounds,
     GameOver,
+    WeaponUnavailable,
+}
+
+/// Errors concerning time controls.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum TimeControlError {
+    IllegalTurnLimit,
+    IllegalTimeBank,
+}
+
+/// Possible outcomes of a recon scan.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ScanOk {
+    Contact,
+    NoContact,
+    ShipCells(Dimension),
+}
+
+/// Errors that can occur when scanning.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ScanError {
+    NotThisPlayersTurn,
+    OutOfBounds,
+    GameOver,
+    IllegalScanSize,
+    NoChargesLeft,
+}
+
+/// Errors that can occur when moving a ship.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum MoveError {
+    NotThisPlayersTurn,
+    GameOver,
+    MovementNotAllowed,
+    UnknownShipType,
+    ShipDamaged,
+    IllegalDistance,
+    IllegalDirection,
+    OutOfBounds,
+    CellOccupied,
+}
+
+/// Errors that can occur when resigning.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ResignError {
+    GameOver,
+}
+
+/// Errors that can occur when offering, accepting or declining a draw.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum DrawError {
+    GameOver,
+    NoDrawOffered,
+}
+
+/// Possible errors when committing a shot in simultaneous mode.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum CommitError {
+    NotSimultaneous,
+    GameOver,
+    OutOfBounds,
+    AlreadyCommitted,
+    /// The player sits out this round, because they hit a mine or used up their shots.
+    SittingOut,
+}
+
+/// Possible errors when resolving a round in simultaneous mode.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ResolveError {
+    NotSimultaneous,
+    GameOver,
+    NotAllCommitted,
+}
+
+/// The results of a round in simultaneous mode.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Round {
+    p1: Option<ShootOk>,
+    p2: Option<ShootOk>,
+}
+
+impl Round {
+    /// Creates a new instance.
+    pub fn new(
+        p1: Option<ShootOk>,
+        p2: Option<ShootOk>,
+    ) -> Self {
+        Round {
+            p1,
+            p2,
+        }
+    }
+
+    /// Returns the result of `player`'s shot, or `None` if they sat out the round.
+    pub fn result(
+        &self,
+        player: Player,
+    ) -> Option<ShootOk> {
+        match player {
+            Player::P1 => self.p1,
+            Player::P2 => self.p2,
+        }
+    }
+}
+
+/// The reasons a game can end for.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum EndReason {
+    FleetDestroyed,
+    Resignation,
+    Timeout,
+    AgreedDraw,
+    ShotLimit,
+    /// Neither player has any ships with firepower left, see `PreGame::set_firepower`.
+    NoFirepower,
+}
+
+/// The outcome of a finished game.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct GameOutcome {
+    winner: Option<Player>,
+    reason: EndReason,
+}
+
+impl GameOutcome {
+    /// Creates a new instance.
+    /// # Parameters
+    /// * `winner` The winner of the game, `None` in case of a draw.
+    /// * `reason` The reason the game ended for.
+    pub fn new(
+        winner: Option<Player>,
+        reason: EndReason,
+    ) -> Self {
+        GameOutcome {
+            winner,
+            reason,
+        }
+    }
+
+    /// Returns the winner, `None` in case of a draw.
+    pub fn winner(&self) -> Option<Player> {
+        self.winner
+    }
+
+    /// Returns the reason the game ended for.
+    pub fn reason(&self) -> EndReason {
+        self.reason
+    }
+
+    /// Returns whether the game ended in a draw.
+    pub fn is_draw(&self) -> bool {
+        self.winner.is_none()
+    }
+}
+
+/// How a puzzle ended.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum PuzzleOutcome {
+    /// The whole fleet was destroyed.
+    Solved,
+    /// The shot budget ran out before the fleet was destroyed.
+    OutOfShots,
+    /// The player ran out of time, see `PreGame::set_time_control`.
+    OutOfTime,
+}
+
+/// Possible errors when generating a solitaire puzzle.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum SolitaireError {
+    NoShipTypes,
+    FleetDoesNotFit,
+}
+
+/// Possible errors when importing a solitaire puzzle from text.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum SolitaireParseError {
+    MissingLine,
+    IllegalNumber,
+    IllegalCell,
+    IllegalLayout,
+    SizeMismatch,
+}
+
+/// Possible errors when recording a game in a placement history.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum HistoryError {
+    GameNotOver,
+    SizeMismatch,
+}
+
+/// Possible errors when importing a placement history from text.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum HistoryParseError {
+    MissingLine,
+    IllegalNumber,
+    SizeMismatch,
+}
+
+/// Possible errors when solving a solitaire puzzle.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum SolveError {
+    NoSolution,
+    MultipleSolutions,
 }
@@ -0,0 +1,23 @@
+//! Optional rules configured through `PreGame` and enforced by `Game`.
+
+use common::MinePenalty;
+use common::Weapon;
+use std::collections::HashMap;
+use time::TurnClock;
+
+/// The optional rules of a game.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Rules {
+    pub turn_clock: Option<TurnClock>,
+    pub shot_limit: Option<usize>,
+    pub weapon_uses: HashMap<Weapon, usize>,
+    pub recon_charges: usize,
+    pub mines: usize,
+    pub mine_penalty: Option<MinePenalty>,
+    pub seed: Option<u64>,
+    pub ship_movement: bool,
+    pub reveal_decoys: bool,
+    pub single_player: bool,
+    pub simultaneous: bool,
+    pub firepower: bool,
+}
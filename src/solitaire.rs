@@ -0,0 +1,597 @@
+//! Battleship solitaire, also known as Bimaru: deduce the position of a fleet from the number of
+//! ship segments in every row and column and a few revealed cells.
+//!
+//! Ships are placed on a `Battlefield` like in a game, including its topology and grid, and no two
+//! ships may touch each other, not even diagonally on a square grid.
+//!
+//! # Text format
+//! Puzzles can be exported with `Solitaire::to_text` and imported with `Solitaire::from_text`.
+//! The format lists the ship lengths of the fleet, optionally the topology and the grid if they
+//! aren't `rectangular` and `square`, the segment counts of the columns and of the rows, and then
+//! the grid, one line per row: `.` is an unknown cell, `~` is revealed water and `#` is a revealed
+//! ship segment.
+//!
+//! ```text
+//! fleet: 2 1
+//! columns: 1 1 1
+//! rows: 2 0 1
+//! .#~
+//! ...
+//! ...
+//! ```
+
+use battlefield::Battlefield;
+use battlefield::Position;
+use common::Dimensional;
+use common::Grid;
+use common::Layer;
+use common::Player::P1;
+use common::ShipTypeContainer;
+use common::Topology;
+use pregame::PreGame;
+use random::Rng;
+use results::SolitaireError;
+use results::SolitaireParseError;
+use results::SolveError;
+use std::collections::HashSet;
+use std::iter::Peekable;
+use super::Dimension;
+
+/// A ship position and the cells a ship occupies there.
+type Candidate = (Position, Vec<(Dimension, Dimension)>);
+
+/// A revealed cell of a solitaire puzzle.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Clue {
+    Water,
+    Ship,
+}
+
+/// A solitaire puzzle, see the module documentation.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Solitaire {
+    fleet: Vec<Dimension>,
+    columns: Vec<Dimension>,
+    rows: Vec<Dimension>,
+    clues: Vec<Vec<Option<Clue>>>,
+    battlefield: Battlefield,
+}
+
+/// The ship cells of a solved solitaire puzzle.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Solution {
+    cells: Vec<Vec<bool>>,
+}
+
+impl Solution {
+    fn new(bf: &Battlefield) -> Self {
+        Solution {
+            cells: (0..bf.height())
+                .map(|y| (0..bf.width())
+                    .map(|x| bf.get_cell(x, y).unwrap().ship_type_id().is_some())
+                    .collect())
+                .collect(),
+        }
+    }
+
+    /// Returns whether the cell (`x`, `y`) holds a ship segment.
+    ///
+    /// # Panics
+    /// Panics if the x and/or y coordinate is out of bounds.
+    pub fn is_ship(
+        &self,
+        x: Dimension,
+        y: Dimension,
+    ) -> bool {
+        self.cells[y][x]
+    }
+}
+
+impl Dimensional for Solution {
+    fn width(&self) -> Dimension {
+        self.cells.first().unwrap().len()
+    }
+
+    fn height(&self) -> Dimension {
+        self.cells.len()
+    }
+}
+
+impl Solitaire {
+    /// Generates a puzzle with a unique solution. The fleet consists of one ship per ship type of
+    /// `setup`, and the puzzle has the size, topology and grid of `setup`'s battlefields.
+    /// # Errors
+    /// * `NoShipTypes` if `setup` has no ship types
+    /// * `FleetDoesNotFit` if the fleet couldn't be placed without ships touching each other
+    ///
+    /// # Examples
+    /// ```
+    /// # use lib_battleship::PreGame;
+    /// # use lib_battleship::solitaire::Solitaire;
+    /// #
+    /// let mut pregame = PreGame::new(6, 6).unwrap();
+    /// pregame.add_ship_type("Cruiser", 3).unwrap();
+    /// pregame.add_ship_type("Corvette", 2).unwrap();
+    /// pregame.add_ship_type("Submarine", 1).unwrap();
+    ///
+    /// let puzzle = Solitaire::generate(&pregame, 42).unwrap();
+    /// println!("{}", puzzle.to_text());
+    /// assert!(puzzle.solve().is_ok());
+    /// ```
+    pub fn generate(
+        setup: &PreGame,
+        seed: u64,
+    ) -> Result<Solitaire, SolitaireError> {
+        let mut fleet: Vec<Dimension> = setup.ship_types().iter()
+            .map(|ship_type| ship_type.length())
+            .collect();
+        if fleet.is_empty() {
+            return Err(SolitaireError::NoShipTypes);
+        }
+        fleet.sort_by(|a, b| b.cmp(a));
+
+        let (width, height) = (setup.width(), setup.height());
+        let mut battlefield = Battlefield::new(width, height).unwrap();
+        battlefield.set_topology(setup.battlefield(P1).topology());
+        battlefield.set_grid(setup.battlefield(P1).grid());
+        let mut rng = Rng::new(seed);
+        let answer = (0..100)
+            .filter_map(|_| place_fleet(&battlefield, &fleet, &mut rng))
+            .next()
+            .ok_or(SolitaireError::FleetDoesNotFit)?;
+
+        let mut puzzle = Solitaire {
+            fleet,
+            columns: (0..width).map(|x| (0..height).filter(|&y| answer.is_ship(x, y)).count()).collect(),
+            rows: (0..height).map(|y| (0..width).filter(|&x| answer.is_ship(x, y)).count()).collect(),
+            clues: vec!(vec!(None; width); height),
+            battlefield,
+        };
+
+        // reveal cells until all other solutions are ruled out
+        loop {
+            let solutions = puzzle.solutions(2);
+            let other = match solutions.into_iter().find(|solution| *solution != answer) {
+                Some(other) => other,
+                None => return Ok(puzzle),
+            };
+            let differences: Vec<(Dimension, Dimension)> = (0..height)
+                .flat_map(|y| (0..width).map(move |x| (x, y)))
+                .filter(|&(x, y)| answer.is_ship(x, y) != other.is_ship(x, y))
+                .collect();
+            let (x, y) = differences[rng.below(differences.len())];
+            puzzle.clues[y][x] = Some(if answer.is_ship(x, y) { Clue::Ship } else { Clue::Water });
+        }
+    }
+
+    /// Returns the ship lengths of the fleet, longest first.
+    pub fn fleet(&self) -> &[Dimension] {
+        &self.fleet
+    }
+
+    /// Returns the topology of the puzzle's battlefield.
+    pub fn topology(&self) -> Topology {
+        self.battlefield.topology()
+    }
+
+    /// Returns the grid of the puzzle's battlefield.
+    pub fn grid(&self) -> Grid {
+        self.battlefield.grid()
+    }
+
+    /// Returns the number of ship segments in column `x`.
+    pub fn column_count(
+        &self,
+        x: Dimension,
+    ) -> Dimension {
+        self.columns[x]
+    }
+
+    /// Returns the number of ship segments in row `y`.
+    pub fn row_count(
+        &self,
+        y: Dimension,
+    ) -> Dimension {
+        self.rows[y]
+    }
+
+    /// Returns what has been revealed about the cell (`x`, `y`), if anything.
+    pub fn clue(
+        &self,
+        x: Dimension,
+        y: Dimension,
+    ) -> Option<Clue> {
+        self.clues[y][x]
+    }
+
+    /// Solves the puzzle.
+    /// # Errors
+    /// * `NoSolution` if the puzzle can't be solved
+    /// * `MultipleSolutions` if the puzzle has more than one solution
+    pub fn solve(&self) -> Result<Solution, SolveError> {
+        let mut solutions = self.solutions(2);
+        match solutions.len() {
+            0 => Err(SolveError::NoSolution),
+            1 => Ok(solutions.remove(0)),
+            _ => Err(SolveError::MultipleSolutions),
+        }
+    }
+
+    /// Finds up to `limit` solutions of the puzzle.
+    pub fn solutions(
+        &self,
+        limit: usize,
+    ) -> Vec<Solution> {
+        let mut solver = Solver {
+            puzzle: self,
+            candidates: self.fleet.iter().map(|&length| candidates(&self.battlefield, length)).collect(),
+            battlefield: self.battlefield.clone(),
+            columns: vec!(0; self.width()),
+            rows: vec!(0; self.height()),
+            limit,
+            solutions: Vec::new(),
+        };
+        let expected: Dimension = self.fleet.iter().sum();
+        if self.columns.iter().sum::<Dimension>() == expected && self.rows.iter().sum::<Dimension>() == expected {
+            solver.place(0, 0);
+        }
+        solver.solutions
+    }
+
+    /// Exports the puzzle in the text format described in the module documentation.
+    pub fn to_text(&self) -> String {
+        let join = |numbers: &[Dimension]| numbers.iter()
+            .map(|n| n.to_string())
+            .collect::<Vec<String>>()
+            .join(" ");
+
+        let mut text = format!("fleet: {}\n", join(&self.fleet));
+        if self.topology() == Topology::Toroidal {
+            text.push_str("topology: toroidal\n");
+        }
+        if self.grid() == Grid::Hexagonal {
+            text.push_str("grid: hexagonal\n");
+        }
+        text.push_str(&format!("columns: {}\nrows: {}\n", join(&self.columns), join(&self.rows)));
+        for row in &self.clues {
+            for clue in row {
+                text.push(match *clue {
+                    None => '.',
+                    Some(Clue::Water) => '~',
+                    Some(Clue::Ship) => '#',
+                });
+            }
+            text.push('\n');
+        }
+        text
+    }
+
+    /// Imports a puzzle in the text format described in the module documentation.
+    /// # Errors
+    /// * `MissingLine` if a line is missing or doesn't start with the expected label
+    /// * `IllegalNumber` if a ship length or segment count isn't a number, or a ship length is 0
+    /// * `IllegalCell` if the grid contains a character other than `.`, `~` and `#`
+    /// * `IllegalLayout` if the topology or the grid is unknown
+    /// * `SizeMismatch` if the grid doesn't match the numbers of columns and rows, or the grid is
+    ///   smaller than 2x2
+    pub fn from_text(text: &str) -> Result<Solitaire, SolitaireParseError> {
+        let mut lines = text.lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .peekable();
+
+        let mut fleet = parse_numbers(lines.next(), "fleet:")?;
+        let topology = match parse_option(&mut lines, "topology:") {
+            None | Some("rectangular") => Topology::Rectangular,
+            Some("toroidal") => Topology::Toroidal,
+            Some(_) => return Err(SolitaireParseError::IllegalLayout),
+        };
+        let grid = match parse_option(&mut lines, "grid:") {
+            None | Some("square") => Grid::Square,
+            Some("hexagonal") => Grid::Hexagonal,
+            Some(_) => return Err(SolitaireParseError::IllegalLayout),
+        };
+        let columns = parse_numbers(lines.next(), "columns:")?;
+        let rows = parse_numbers(lines.next(), "rows:")?;
+        if fleet.contains(&0) {
+            return Err(SolitaireParseError::IllegalNumber);
+        }
+        fleet.sort_by(|a, b| b.cmp(a));
+
+        let clues = lines
+            .map(|line| line.chars().map(|c| match c {
+                '.' => Ok(None),
+                '~' => Ok(Some(Clue::Water)),
+                '#' => Ok(Some(Clue::Ship)),
+                _ => Err(SolitaireParseError::IllegalCell),
+            }).collect::<Result<Vec<Option<Clue>>, SolitaireParseError>>())
+            .collect::<Result<Vec<Vec<Option<Clue>>>, SolitaireParseError>>()?;
+        if clues.len() != rows.len() || clues.iter().any(|row| row.len() != columns.len()) {
+            return Err(SolitaireParseError::SizeMismatch);
+        }
+        let mut battlefield = Battlefield::new(columns.len(), rows.len())
+            .map_err(|_| SolitaireParseError::SizeMismatch)?;
+        battlefield.set_topology(topology);
+        battlefield.set_grid(grid);
+
+        Ok(Solitaire {
+            fleet,
+            columns,
+            rows,
+            clues,
+            battlefield,
+        })
+    }
+}
+
+impl Dimensional for Solitaire {
+    fn width(&self) -> Dimension {
+        self.battlefield.width()
+    }
+
+    fn height(&self) -> Dimension {
+        self.battlefield.height()
+    }
+}
+
+fn parse_numbers(
+    line: Option<&str>,
+    label: &str,
+) -> Result<Vec<Dimension>, SolitaireParseError> {
+    let line = line.ok_or(SolitaireParseError::MissingLine)?;
+    if !line.starts_with(label) {
+        return Err(SolitaireParseError::MissingLine);
+    }
+    line[label.len()..].split_whitespace()
+        .map(|number| number.parse().map_err(|_| SolitaireParseError::IllegalNumber))
+        .collect()
+}
+
+/// Consumes the next line if it starts with `label` and returns the rest of it.
+fn parse_option<'a, I>(
+    lines: &mut Peekable<I>,
+    label: &str,
+) -> Option<&'a str>
+    where I: Iterator<Item = &'a str>
+{
+    if !lines.peek()?.starts_with(label) {
+        return None;
+    }
+    lines.next().map(|line| line[label.len()..].trim())
+}
+
+/// Returns every position of a ship of `length` on the empty `bf`, with the cells it occupies.
+/// Positions covering the same cells, e.g. on a toroidal battlefield, are only listed once.
+fn candidates(
+    bf: &Battlefield,
+    length: Dimension,
+) -> Vec<Candidate> {
+    let mut seen = HashSet::new();
+    bf.free_positions(length, Layer::Surface).into_iter()
+        .map(|position| (position, bf.ship_coords(length, position).unwrap()))
+        .filter(|(_, coords)| {
+            let mut cells = coords.clone();
+            cells.sort();
+            seen.insert(cells)
+        })
+        .collect()
+}
+
+/// Returns whether the cells at `coords` and all cells touching them are free of ships on `bf`.
+fn is_clear(
+    bf: &Battlefield,
+    coords: &[(Dimension, Dimension)],
+) -> bool {
+    let offsets = bf.grid().surrounding_offsets();
+    let surrounding: Vec<(Dimension, Dimension)> = coords.iter()
+        .flat_map(|&(x, y)| offsets.iter().filter_map(move |&(dx, dy)| bf.offset(x, y, dx, dy)))
+        .chain(coords.iter().cloned())
+        .collect();
+    bf.is_free(Layer::Surface, &surrounding, None)
+}
+
+/// Places `fleet` on a copy of `bf` at random positions where no ships touch, or returns `None`
+/// if it ran out of room.
+fn place_fleet(
+    bf: &Battlefield,
+    fleet: &[Dimension],
+    rng: &mut Rng,
+) -> Option<Solution> {
+    let mut bf = bf.clone();
+    for (id, &length) in fleet.iter().enumerate() {
+        let free: Vec<Candidate> = candidates(&bf, length).into_iter()
+            .filter(|(_, coords)| is_clear(&bf, coords))
+            .collect();
+        if free.is_empty() {
+            return None;
+        }
+        let (position, ref coords) = free[rng.below(free.len())];
+        bf.place_ship(id, Layer::Surface, position, coords);
+    }
+    Some(Solution::new(&bf))
+}
+
+/// Backtracking search over the positions of the ships, longest ships first.
+struct Solver<'a> {
+    puzzle: &'a Solitaire,
+    candidates: Vec<Vec<Candidate>>,
+    battlefield: Battlefield,
+    columns: Vec<Dimension>,
+    rows: Vec<Dimension>,
+    limit: usize,
+    solutions: Vec<Solution>,
+}
+
+impl<'a> Solver<'a> {
+    /// Places ship `index` of the fleet. Ships of equal length are placed in the order of their
+    /// candidates, starting at `first`, so that each arrangement is only found once.
+    fn place(
+        &mut self,
+        index: usize,
+        first: usize,
+    ) {
+        if self.solutions.len() >= self.limit {
+            return;
+        }
+        let puzzle = self.puzzle;
+        let length = match puzzle.fleet.get(index) {
+            Some(&length) => length,
+            None => {
+                let solution = Solution::new(&self.battlefield);
+                let clues_met = (0..puzzle.height())
+                    .flat_map(|y| (0..puzzle.width()).map(move |x| (x, y)))
+                    .all(|(x, y)| puzzle.clue(x, y) != Some(Clue::Ship) || solution.is_ship(x, y));
+                if clues_met {
+                    self.solutions.push(solution);
+                }
+                return;
+            },
+        };
+
+        for candidate in first..self.candidates[index].len() {
+            let (position, coords) = self.candidates[index][candidate].clone();
+            if !self.fits(&coords) {
+                continue;
+            }
+            self.battlefield.place_ship(index, Layer::Surface, position, &coords);
+            self.count(&coords, true);
+            let next_first = match puzzle.fleet.get(index + 1) {
+                Some(&next) if next == length => candidate + 1,
+                _ => 0,
+            };
+            self.place(index + 1, next_first);
+            self.battlefield.remove_ship(index);
+            self.count(&coords, false);
+        }
+    }
+
+    fn fits(
+        &self,
+        coords: &[(Dimension, Dimension)],
+    ) -> bool {
+        let mut columns = self.columns.clone();
+        let mut rows = self.rows.clone();
+        for &(x, y) in coords {
+            columns[x] += 1;
+            rows[y] += 1;
+            if columns[x] > self.puzzle.columns[x] || rows[y] > self.puzzle.rows[y]
+                || self.puzzle.clue(x, y) == Some(Clue::Water) {
+                return false;
+            }
+        }
+        is_clear(&self.battlefield, coords)
+    }
+
+    fn count(
+        &mut self,
+        coords: &[(Dimension, Dimension)],
+        ship: bool,
+    ) {
+        for &(x, y) in coords {
+            if ship {
+                self.columns[x] += 1;
+                self.rows[y] += 1;
+            } else {
+                self.columns[x] -= 1;
+                self.rows[y] -= 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::Dimensional;
+    use common::Grid;
+    use common::Topology;
+    use pregame::PreGame;
+    use results::SolitaireError;
+    use results::SolitaireParseError;
+    use results::SolveError;
+    use super::Clue;
+    use super::Solitaire;
+
+    const TEXT: &str = "fleet: 2 1\ncolumns: 1 1 1\nrows: 2 0 1\n.#~\n...\n...\n";
+
+    #[test]
+    fn should_import_and_export_text() {
+        let puzzle = Solitaire::from_text(TEXT).unwrap();
+
+        assert_eq!(3, puzzle.width());
+        assert_eq!(&[2, 1], puzzle.fleet());
+        assert_eq!(1, puzzle.column_count(2));
+        assert_eq!(0, puzzle.row_count(1));
+        assert_eq!(Some(Clue::Ship), puzzle.clue(1, 0));
+        assert_eq!(Some(Clue::Water), puzzle.clue(2, 0));
+        assert_eq!(None, puzzle.clue(0, 0));
+        assert_eq!(TEXT, puzzle.to_text());
+    }
+
+    #[test]
+    fn should_reject_malformed_text() {
+        assert_eq!(Err(SolitaireParseError::MissingLine), Solitaire::from_text("fleet: 1\n"));
+        assert_eq!(Err(SolitaireParseError::IllegalNumber), Solitaire::from_text("fleet: x\ncolumns: 1 0\nrows: 1 0\n..\n..\n"));
+        assert_eq!(Err(SolitaireParseError::IllegalCell), Solitaire::from_text("fleet: 1\ncolumns: 1 0\nrows: 1 0\n.x\n..\n"));
+        assert_eq!(Err(SolitaireParseError::SizeMismatch), Solitaire::from_text("fleet: 1\ncolumns: 1 0\nrows: 1 0\n..\n"));
+        assert_eq!(Err(SolitaireParseError::IllegalLayout), Solitaire::from_text("fleet: 1\ngrid: round\ncolumns: 1 0\nrows: 1 0\n..\n..\n"));
+    }
+
+    #[test]
+    fn should_solve_puzzles() {
+        let solution = Solitaire::from_text(TEXT).unwrap().solve().unwrap();
+        assert!(solution.is_ship(0, 0));
+        assert!(solution.is_ship(1, 0));
+        assert!(solution.is_ship(2, 2));
+        assert!(!solution.is_ship(2, 0));
+
+        let ambiguous = Solitaire::from_text("fleet: 1 1\ncolumns: 1 0 1\nrows: 1 0 1\n...\n...\n...\n").unwrap();
+        assert_eq!(2, ambiguous.solutions(5).len());
+        assert_eq!(Err(SolveError::MultipleSolutions), ambiguous.solve());
+        let touching = Solitaire::from_text("fleet: 1 1\ncolumns: 1 1\nrows: 1 1\n..\n..\n").unwrap();
+        assert_eq!(Err(SolveError::NoSolution), touching.solve());
+    }
+
+    #[test]
+    fn should_follow_the_topology_and_grid() {
+        let torus = Solitaire::from_text("fleet: 1 1\ntopology: toroidal\ncolumns: 1 0 1\nrows: 1 0 1\n...\n...\n...\n").unwrap();
+        assert_eq!(Topology::Toroidal, torus.topology());
+        assert_eq!(Err(SolveError::NoSolution), torus.solve());
+
+        let hex = Solitaire::from_text("fleet: 1 1\ngrid: hexagonal\ncolumns: 1 1\nrows: 1 1\n..\n..\n").unwrap();
+        let solution = hex.solve().unwrap();
+        assert!(solution.is_ship(0, 0));
+        assert!(solution.is_ship(1, 1));
+        assert_eq!(hex, Solitaire::from_text(&hex.to_text()).unwrap());
+    }
+
+    #[test]
+    fn should_generate_unique_puzzles() {
+        let mut pregame = PreGame::new(6, 6).unwrap();
+        pregame.add_ship_type("Cruiser", 3).unwrap();
+        pregame.add_ship_type("Corvette", 2).unwrap();
+        pregame.add_ship_type("Frigate", 2).unwrap();
+        pregame.add_ship_type("Submarine", 1).unwrap();
+
+        for seed in 0..5 {
+            let puzzle = Solitaire::generate(&pregame, seed).unwrap();
+            assert_eq!(1, puzzle.solutions(2).len());
+            assert_eq!(puzzle, Solitaire::from_text(&puzzle.to_text()).unwrap());
+        }
+
+        pregame.set_topology(Topology::Toroidal).unwrap();
+        pregame.set_grid(Grid::Hexagonal).unwrap();
+        let puzzle = Solitaire::generate(&pregame, 1).unwrap();
+        assert_eq!(1, puzzle.solutions(2).len());
+        assert_eq!(puzzle, Solitaire::from_text(&puzzle.to_text()).unwrap());
+    }
+
+    #[test]
+    fn should_fail_if_the_fleet_does_not_fit() {
+        let mut pregame = PreGame::new(2, 2).unwrap();
+        assert_eq!(Err(SolitaireError::NoShipTypes), Solitaire::generate(&pregame, 1));
+
+        pregame.add_ship_type("Submarine", 1).unwrap();
+        pregame.add_ship_type("Dinghy", 1).unwrap();
+        assert_eq!(Err(SolitaireError::FleetDoesNotFit), Solitaire::generate(&pregame, 1));
+    }
+}
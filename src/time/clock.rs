@@ -0,0 +1,81 @@
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+/// A monotonic source of time for the game clock.
+pub trait Clock: Send + Sync {
+    /// Returns the time passed since an arbitrary but fixed point in the past.
+    fn now(&self) -> Duration;
+}
+
+/// `Clock` backed by the system's monotonic clock.
+#[derive(Debug)]
+pub struct SystemClock {
+    origin: Instant,
+}
+
+impl SystemClock {
+    /// Creates a new instance, starting at zero.
+    pub fn new() -> Self {
+        SystemClock {
+            origin: Instant::now(),
+        }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        SystemClock::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.origin.elapsed()
+    }
+}
+
+/// `Clock` that only moves when told to, e.g. to drive time controls in tests.
+#[derive(Debug, Default)]
+pub struct ManualClock {
+    now: Mutex<Duration>,
+}
+
+impl ManualClock {
+    /// Creates a new instance, starting at zero.
+    pub fn new() -> Self {
+        ManualClock::default()
+    }
+
+    /// Moves the clock forward by `duration`.
+    pub fn advance(
+        &self,
+        duration: Duration,
+    ) {
+        let mut now = self.now.lock().unwrap();
+        *now += duration;
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        *self.now.lock().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Clock;
+    use super::ManualClock;
+    use std::time::Duration;
+
+    #[test]
+    fn manual_clock_should_only_move_when_advanced() {
+        let clock = ManualClock::new();
+        assert_eq!(Duration::from_secs(0), clock.now());
+
+        clock.advance(Duration::from_secs(3));
+        clock.advance(Duration::from_secs(4));
+        assert_eq!(Duration::from_secs(7), clock.now());
+    }
+}
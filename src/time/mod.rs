@@ -0,0 +1,12 @@
+//! Time controls and the clocks driving them.
+
+pub use self::clock::Clock;
+pub use self::clock::ManualClock;
+pub use self::clock::SystemClock;
+pub use self::time_control::TimeControl;
+pub(crate) use self::turn_clock::Timeout;
+pub(crate) use self::turn_clock::TurnClock;
+
+mod clock;
+mod time_control;
+mod turn_clock;
@@ -0,0 +1,73 @@
+use results::TimeControlError;
+use results::TimeControlError::*;
+use std::time::Duration;
+
+/// Time controls for a game of battleship.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct TimeControl {
+    turn_limit: Option<Duration>,
+    time_bank: Option<Duration>,
+    increment: Duration,
+}
+
+impl TimeControl {
+    /// Creates a new instance.
+    /// # Parameters
+    /// * `turn_limit` The maximum length of a single turn. A player exceeding it forfeits the turn.
+    /// * `time_bank` The total time available to each player. A player running out of it loses the game.
+    /// * `increment` The time added to a player's time bank after each completed turn.
+    ///
+    /// # Errors
+    /// * `IllegalTurnLimit` If `turn_limit` is zero.
+    /// * `IllegalTimeBank` If `time_bank` is zero.
+    pub fn new(
+        turn_limit: Option<Duration>,
+        time_bank: Option<Duration>,
+        increment: Duration,
+    ) -> Result<TimeControl, TimeControlError> {
+        let zero = Duration::from_secs(0);
+        if turn_limit == Some(zero) {
+            Err(IllegalTurnLimit)
+        } else if time_bank == Some(zero) {
+            Err(IllegalTimeBank)
+        } else {
+            Ok(TimeControl {
+                turn_limit,
+                time_bank,
+                increment,
+            })
+        }
+    }
+
+    /// Returns the turn limit, if any.
+    pub fn turn_limit(&self) -> Option<Duration> {
+        self.turn_limit
+    }
+
+    /// Returns the initial time bank of each player, if any.
+    pub fn time_bank(&self) -> Option<Duration> {
+        self.time_bank
+    }
+
+    /// Returns the increment.
+    pub fn increment(&self) -> Duration {
+        self.increment
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use results::TimeControlError::*;
+    use std::time::Duration;
+    use super::TimeControl;
+
+    #[test]
+    fn constructor_should_reject_zero_durations() {
+        let zero = Duration::from_secs(0);
+        let one = Duration::from_secs(1);
+
+        assert_eq!(Err(IllegalTurnLimit), TimeControl::new(Some(zero), None, one));
+        assert_eq!(Err(IllegalTimeBank), TimeControl::new(None, Some(zero), one));
+        assert!(TimeControl::new(Some(one), Some(one), zero).is_ok());
+    }
+}
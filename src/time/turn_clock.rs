@@ -0,0 +1,265 @@
+use common::Player::{self, P1};
+use std::cmp;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+use super::Clock;
+use super::TimeControl;
+
+/// The ways a player can run out of time.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Timeout {
+    /// The player exceeded the turn limit and forfeits the turn.
+    Turn,
+    /// The player's time bank ran out and they forfeit the game.
+    Game,
+}
+
+/// Keeps track of the time used by both players.
+#[derive(Clone)]
+pub struct TurnClock {
+    source: Arc<dyn Clock>,
+    control: TimeControl,
+    turn_start: Duration,
+    bank_p1: Option<Duration>,
+    bank_p2: Option<Duration>,
+}
+
+impl TurnClock {
+    pub fn new(
+        control: TimeControl,
+        source: Arc<dyn Clock>,
+    ) -> Self {
+        let turn_start = source.now();
+        TurnClock {
+            source,
+            control,
+            turn_start,
+            bank_p1: control.time_bank(),
+            bank_p2: control.time_bank(),
+        }
+    }
+
+    /// Returns the time control the clock enforces.
+    pub fn control(&self) -> TimeControl {
+        self.control
+    }
+
+    /// Starts the first turn of the game.
+    pub fn start(&mut self) {
+        self.turn_start = self.source.now();
+    }
+
+    /// Checks whether `player`, whose turn it currently is, has run out of time. A forfeited
+    /// turn is charged to the player's time bank and the next turn is considered to have
+    /// started when the turn limit was reached.
+    pub fn check(
+        &mut self,
+        player: Player,
+    ) -> Option<Timeout> {
+        let timeout = self.check_simultaneous(player);
+        if let (Some(Timeout::Turn), Some(limit)) = (timeout, self.control.turn_limit()) {
+            self.turn_start += limit;
+        }
+        timeout
+    }
+
+    /// Like `check`, but for simultaneous turns, where both players' turns run at the same
+    /// time: a forfeited turn is charged to the player's time bank, but the turn keeps running
+    /// for the other player until the next one is started, see `start`.
+    pub fn check_simultaneous(
+        &mut self,
+        player: Player,
+    ) -> Option<Timeout> {
+        let elapsed = self.elapsed();
+        if let Some(bank) = self.bank(player) {
+            if elapsed >= bank {
+                self.set_bank(player, Duration::from_secs(0));
+                return Some(Timeout::Game);
+            }
+        }
+
+        match self.control.turn_limit() {
+            Some(limit) if elapsed >= limit => {
+                self.charge(player, limit);
+                Some(Timeout::Turn)
+            },
+            _ => None,
+        }
+    }
+
+    /// Ends the turn of `player`, charging the time used and crediting the increment.
+    pub fn end_turn(
+        &mut self,
+        player: Player,
+    ) {
+        self.end_simultaneous_turn(player);
+        self.turn_start = self.source.now();
+    }
+
+    /// Like `end_turn`, but for simultaneous turns: the turn keeps running for the other player
+    /// until the next one is started, see `start`.
+    pub fn end_simultaneous_turn(
+        &mut self,
+        player: Player,
+    ) {
+        let elapsed = self.elapsed();
+        self.charge(player, elapsed);
+        if let Some(bank) = self.bank(player) {
+            self.set_bank(player, bank + self.control.increment());
+        }
+    }
+
+    /// Returns the time left in `player`'s time bank, given whose turn it is.
+    pub fn remaining(
+        &self,
+        player: Player,
+        current_player: Player,
+    ) -> Option<Duration> {
+        self.bank(player).map(|bank| {
+            if player == current_player {
+                bank.checked_sub(self.elapsed()).unwrap_or_default()
+            } else {
+                bank
+            }
+        })
+    }
+
+    /// Returns the time left in the current turn.
+    pub fn remaining_in_turn(&self) -> Option<Duration> {
+        self.control.turn_limit()
+            .map(|limit| limit.checked_sub(self.elapsed()).unwrap_or_default())
+    }
+
+    fn elapsed(&self) -> Duration {
+        let now = cmp::max(self.source.now(), self.turn_start);
+        now - self.turn_start
+    }
+
+    fn charge(
+        &mut self,
+        player: Player,
+        duration: Duration,
+    ) {
+        if let Some(bank) = self.bank(player) {
+            self.set_bank(player, bank.checked_sub(duration).unwrap_or_default());
+        }
+    }
+
+    fn bank(
+        &self,
+        player: Player,
+    ) -> Option<Duration> {
+        if player == P1 {
+            self.bank_p1
+        } else {
+            self.bank_p2
+        }
+    }
+
+    fn set_bank(
+        &mut self,
+        player: Player,
+        bank: Duration,
+    ) {
+        if player == P1 {
+            self.bank_p1 = Some(bank);
+        } else {
+            self.bank_p2 = Some(bank);
+        }
+    }
+}
+
+impl PartialEq for TurnClock {
+    fn eq(
+        &self,
+        other: &TurnClock,
+    ) -> bool {
+        Arc::ptr_eq(&self.source, &other.source)
+            && self.control == other.control
+            && self.turn_start == other.turn_start
+            && self.bank_p1 == other.bank_p1
+            && self.bank_p2 == other.bank_p2
+    }
+}
+
+impl fmt::Debug for TurnClock {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter,
+    ) -> fmt::Result {
+        f.debug_struct("TurnClock")
+            .field("control", &self.control)
+            .field("turn_start", &self.turn_start)
+            .field("bank_p1", &self.bank_p1)
+            .field("bank_p2", &self.bank_p2)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::Player::*;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use super::Timeout;
+    use super::TurnClock;
+    use time::ManualClock;
+    use time::TimeControl;
+
+    fn secs(n: u64) -> Duration {
+        Duration::from_secs(n)
+    }
+
+    #[test]
+    fn should_forfeit_turn_and_charge_bank() {
+        let source = Arc::new(ManualClock::new());
+        let control = TimeControl::new(Some(secs(10)), Some(secs(100)), secs(0)).unwrap();
+        let mut clock = TurnClock::new(control, source.clone());
+
+        source.advance(secs(9));
+        assert_eq!(None, clock.check(P1));
+        source.advance(secs(3));
+        assert_eq!(Some(Timeout::Turn), clock.check(P1));
+        assert_eq!(Some(secs(90)), clock.remaining(P1, P2));
+        assert_eq!(Some(secs(98)), clock.remaining(P2, P2));
+    }
+
+    #[test]
+    fn should_forfeit_game_when_bank_runs_out() {
+        let source = Arc::new(ManualClock::new());
+        let control = TimeControl::new(None, Some(secs(5)), secs(0)).unwrap();
+        let mut clock = TurnClock::new(control, source.clone());
+
+        source.advance(secs(5));
+        assert_eq!(Some(Timeout::Game), clock.check(P1));
+        assert_eq!(Some(secs(0)), clock.remaining(P1, P1));
+    }
+
+    #[test]
+    fn should_keep_simultaneous_turns_running() {
+        let source = Arc::new(ManualClock::new());
+        let control = TimeControl::new(Some(secs(10)), Some(secs(100)), secs(0)).unwrap();
+        let mut clock = TurnClock::new(control, source.clone());
+
+        source.advance(secs(4));
+        clock.end_simultaneous_turn(P1);
+        source.advance(secs(7));
+        assert_eq!(Some(Timeout::Turn), clock.check_simultaneous(P2));
+        assert_eq!(Some(secs(96)), clock.remaining(P1, P2));
+        assert_eq!(Some(secs(90)), clock.remaining(P2, P1));
+        assert_eq!(Some(secs(0)), clock.remaining_in_turn());
+    }
+
+    #[test]
+    fn should_credit_increment_after_turn() {
+        let source = Arc::new(ManualClock::new());
+        let control = TimeControl::new(None, Some(secs(60)), secs(5)).unwrap();
+        let mut clock = TurnClock::new(control, source.clone());
+
+        source.advance(secs(20));
+        clock.end_turn(P1);
+        assert_eq!(Some(secs(45)), clock.remaining(P1, P2));
+        assert_eq!(Some(secs(60)), clock.remaining(P2, P2));
+    }
+}
@@ -0,0 +1,402 @@
+//! Bot-vs-bot matches, e.g. to tune a bot against others.
+//!
+//! ```
+//! use lib_battleship::PreGame;
+//! use lib_battleship::ai::{HuntTargetBot, PlacementStrategy, RandomBot};
+//! use lib_battleship::tournament::{play_match, Contestant};
+//!
+//! let setup = || {
+//!     let mut pregame = PreGame::new(6, 6).unwrap();
+//!     pregame.add_ship_type("Cruiser", 3).unwrap();
+//!     pregame.add_ship_type("Corvette", 2).unwrap();
+//!     pregame
+//! };
+//! let hunter = Contestant::new("hunt-target", PlacementStrategy::Random,
+//!     |pregame: &PreGame, seed| Box::new(HuntTargetBot::new(pregame, seed)));
+//! let random = Contestant::new("random", PlacementStrategy::Random,
+//!     |_: &PreGame, seed| Box::new(RandomBot::new(seed)));
+//!
+//! let report = play_match(setup, &hunter, &random, 20, 42).unwrap();
+//! assert_eq!(20, report.games());
+//! println!("{} won {:.0}%", report.first().name(), 100.0 * report.first().win_rate());
+//! ```
+
+use ai::PlacementStrategy;
+use ai::Shooter;
+use ai::View;
+use common::Player;
+use common::Player::*;
+use game::Game;
+use pregame::PreGame;
+use random::Rng;
+use results::MatchError;
+use results::MatchError::*;
+use results::Verdict;
+
+/// The z-score of a 95% confidence interval.
+const Z: f64 = 1.96;
+
+/// Creates a contestant's shooter from the game's setup and a seed.
+type ShooterFactory<'a> = dyn Fn(&PreGame, u64) -> Box<dyn Shooter> + 'a;
+
+/// A bot taking part in a match: a name, a way to place its fleet and a way to create its
+/// shooter for every game.
+pub struct Contestant<'a> {
+    name: String,
+    placement: PlacementStrategy,
+    shooter: Box<ShooterFactory<'a>>,
+}
+
+impl<'a> Contestant<'a> {
+    /// Creates a new instance. `shooter` is called with the game's setup and a seed at the
+    /// start of every game.
+    pub fn new<F>(
+        name: &str,
+        placement: PlacementStrategy,
+        shooter: F,
+    ) -> Self
+        where F: Fn(&PreGame, u64) -> Box<dyn Shooter> + 'a
+    {
+        Contestant {
+            name: name.to_string(),
+            placement,
+            shooter: Box::new(shooter),
+        }
+    }
+
+    /// Returns the contestant's name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// A contestant's results in a match.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Record {
+    name: String,
+    games: usize,
+    shots_to_win: Vec<usize>,
+}
+
+impl Record {
+    fn new(
+        name: &str,
+        games: usize,
+    ) -> Self {
+        Record {
+            name: name.to_string(),
+            games,
+            shots_to_win: Vec::new(),
+        }
+    }
+
+    /// Returns the name of the contestant the record belongs to.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the number of games won.
+    pub fn wins(&self) -> usize {
+        self.shots_to_win.len()
+    }
+
+    /// Returns the share of games won, between 0 and 1.
+    pub fn win_rate(&self) -> f64 {
+        if self.games == 0 {
+            0.0
+        } else {
+            self.wins() as f64 / self.games as f64
+        }
+    }
+
+    /// Returns the 95% confidence interval of the win rate (Wilson score interval).
+    pub fn win_rate_interval(&self) -> (f64, f64) {
+        if self.games == 0 {
+            return (0.0, 1.0);
+        }
+        let n = self.games as f64;
+        let p = self.win_rate();
+        let denominator = 1.0 + Z * Z / n;
+        let centre = (p + Z * Z / (2.0 * n)) / denominator;
+        let spread = Z * (p * (1.0 - p) / n + Z * Z / (4.0 * n * n)).sqrt() / denominator;
+        ((centre - spread).max(0.0), (centre + spread).min(1.0))
+    }
+
+    /// Returns the number of shots it took to win each game won, in the order of the games.
+    pub fn shots_to_win(&self) -> &[usize] {
+        &self.shots_to_win
+    }
+
+    /// Returns the mean number of shots it took to win, or `None` without wins.
+    pub fn mean_shots_to_win(&self) -> Option<f64> {
+        if self.shots_to_win.is_empty() {
+            return None;
+        }
+        let sum: usize = self.shots_to_win.iter().sum();
+        Some(sum as f64 / self.wins() as f64)
+    }
+
+    /// Returns the 95% confidence interval of the mean number of shots it took to win, or
+    /// `None` without wins.
+    pub fn shots_to_win_interval(&self) -> Option<(f64, f64)> {
+        let mean = self.mean_shots_to_win()?;
+        if self.wins() < 2 {
+            return Some((mean, mean));
+        }
+        let n = self.wins() as f64;
+        let variance = self.shots_to_win.iter()
+            .map(|&shots| (shots as f64 - mean).powi(2))
+            .sum::<f64>() / (n - 1.0);
+        let spread = Z * (variance / n).sqrt();
+        Some((mean - spread, mean + spread))
+    }
+}
+
+/// The results of a match.
+#[derive(Clone, PartialEq, Debug)]
+pub struct MatchReport {
+    records: [Record; 2],
+    draws: usize,
+}
+
+impl MatchReport {
+    /// Returns the number of games played.
+    pub fn games(&self) -> usize {
+        self.records[0].games
+    }
+
+    /// Returns the number of games that ended without a winner.
+    pub fn draws(&self) -> usize {
+        self.draws
+    }
+
+    /// Returns the results of the first contestant.
+    pub fn first(&self) -> &Record {
+        &self.records[0]
+    }
+
+    /// Returns the results of the second contestant.
+    pub fn second(&self) -> &Record {
+        &self.records[1]
+    }
+}
+
+/// Plays `games` games between `first` and `second`. Each game is set up by `setup`, which
+/// must add the ship types and may configure the rules, then both contestants place their
+/// fleets and shoot until the game is over. The contestants take turns at being player 1, who
+/// shoots first. The same seed always leads to the same results.
+///
+/// # Errors
+/// * `FleetDoesNotFit` if a contestant's placement strategy couldn't place its fleet
+/// * `IllegalSetup` if a game couldn't be started
+/// * `IllegalShot` if a contestant's shot was rejected by the game
+/// * `NoShot` if a contestant ran out of cells to shoot at while the game was still running
+pub fn play_match<F>(
+    setup: F,
+    first: &Contestant,
+    second: &Contestant,
+    games: usize,
+    seed: u64,
+) -> Result<MatchReport, MatchError>
+    where F: Fn() -> PreGame
+{
+    let mut rng = Rng::new(seed);
+    let mut report = MatchReport {
+        records: [Record::new(&first.name, games), Record::new(&second.name, games)],
+        draws: 0,
+    };
+
+    for game_index in 0..games {
+        let swapped = game_index % 2 == 1;
+        // indexed by player
+        let contestants = if swapped { [second, first] } else { [first, second] };
+        let mut pregame = setup();
+        for &player in &[P1, P2] {
+            let placements = contestants[player_index(player)].placement
+                .place(&pregame, player, rng.next_u64())
+                .ok_or(FleetDoesNotFit)?;
+            for placement in placements {
+                placement.apply(&mut pregame, player).map_err(|_| FleetDoesNotFit)?;
+            }
+        }
+        let mut first_shooter = (contestants[0].shooter)(&pregame, rng.next_u64());
+        let mut second_shooter = (contestants[1].shooter)(&pregame, rng.next_u64());
+        let mut game = pregame.start().map_err(|_| IllegalSetup)?;
+        let shots = play_game(&mut game, [&mut *first_shooter, &mut *second_shooter])?;
+
+        match game.get_winner() {
+            Some(winner) => {
+                let index = player_index(winner);
+                let record = if swapped { 1 - index } else { index };
+                report.records[record].shots_to_win.push(shots[index]);
+            },
+            None => report.draws += 1,
+        }
+    }
+
+    Ok(report)
+}
+
+/// Plays `game` until it's over, `shooters` taking the shots of player 1 and player 2. Each
+/// shooter learns the results of its own shots, the opponent's shots at its battlefield and how
+/// the game ended. Returns the number of shots each player took. With simultaneous turns, both
+/// shooters pick their shot before the round is resolved, see `Game::commit_shot`.
+///
+/// # Errors
+/// * `IllegalShot` if a shot was rejected by the game
+/// * `NoShot` if a shooter ran out of cells to shoot at while the game was still running
+pub fn play_game(
+    game: &mut Game,
+    shooters: [&mut dyn Shooter; 2],
+) -> Result<[usize; 2], MatchError> {
+    let mut shots = [0, 0];
+    while game.outcome().is_none() {
+        if game.is_simultaneous() {
+            let mut targets = [None, None];
+            for &player in &[P1, P2] {
+                if game.is_sitting_out(player) {
+                    continue;
+                }
+                let index = player_index(player);
+                let (x, y) = shooters[index].next_shot(&View::of_opponent(game, player)).ok_or(NoShot)?;
+                game.commit_shot(player, x, y).map_err(|_| IllegalShot)?;
+                targets[index] = Some((x, y));
+            }
+            let round = game.resolve_round().map_err(|_| IllegalShot)?;
+            for &player in &[P1, P2] {
+                let index = player_index(player);
+                if let (Some((x, y)), Some(result)) = (targets[index], round.result(player)) {
+                    shooters[index].record_result(x, y, result);
+                    shooters[1 - index].record_opponent_shot(x, y, result);
+                    shots[index] += 1;
+                }
+            }
+            continue;
+        }
+
+        let player = game.current_player();
+        let index = player_index(player);
+        let (x, y) = shooters[index].next_shot(&View::of_opponent(game, player)).ok_or(NoShot)?;
+        let result = game.shoot(player.next(), x, y).map_err(|_| IllegalShot)?;
+        shooters[index].record_result(x, y, result);
+        shooters[1 - index].record_opponent_shot(x, y, result);
+        shots[index] += 1;
+    }
+
+    for &player in &[P1, P2] {
+        let verdict = match game.get_winner() {
+            Some(winner) if winner == player => Verdict::Win,
+            Some(_) => Verdict::Loss,
+            None => Verdict::Draw,
+        };
+        shooters[player_index(player)].game_over(verdict);
+    }
+    Ok(shots)
+}
+
+fn player_index(player: Player) -> usize {
+    if player == P1 { 0 } else { 1 }
+}
+
+#[cfg(test)]
+mod test {
+    use ai::AdaptiveBot;
+    use ai::Difficulty;
+    use ai::HuntTargetBot;
+    use ai::PlacementHistory;
+    use ai::PlacementStrategy::*;
+    use ai::ProbabilityBot;
+    use ai::RandomBot;
+    use ai::TunableBot;
+    use common::ShipType;
+    use pregame::PreGame;
+    use results::MatchError::*;
+    use super::Contestant;
+    use super::play_match;
+
+    fn setup() -> PreGame {
+        let mut pregame = PreGame::new(6, 6).unwrap();
+        pregame.add_ship_type("Cruiser", 3).unwrap();
+        pregame.add_ship_type("Corvette", 2).unwrap();
+
+        pregame
+    }
+
+    #[test]
+    fn should_report_a_reproducible_match() {
+        let hunter = Contestant::new("hunter", EdgeHugging,
+            |pregame: &PreGame, seed| Box::new(HuntTargetBot::new(pregame, seed)));
+        let random = Contestant::new("random", Random, |_: &PreGame, seed| Box::new(RandomBot::new(seed)));
+
+        let report = play_match(setup, &hunter, &random, 40, 1).unwrap();
+        assert_eq!(40, report.games());
+        assert_eq!(40, report.first().wins() + report.second().wins() + report.draws());
+        assert_eq!("hunter", report.first().name());
+        assert!(report.first().win_rate() > report.second().win_rate());
+
+        let (low, high) = report.first().win_rate_interval();
+        assert!(low < report.first().win_rate() && report.first().win_rate() < high);
+        let mean = report.first().mean_shots_to_win().unwrap();
+        let (low, high) = report.first().shots_to_win_interval().unwrap();
+        assert!(5.0 <= low && low <= mean && mean <= high && high <= 36.0);
+
+        assert_eq!(report, play_match(setup, &hunter, &random, 40, 1).unwrap());
+    }
+
+    #[test]
+    fn should_finish_games_against_armored_ships() {
+        let armored = || {
+            let mut pregame = setup();
+            pregame.add_custom_ship_type(ShipType::new(0, "Ironclad", 2).with_armor(2)).unwrap();
+            pregame
+        };
+        let history = PlacementHistory::new(6, 6);
+        let contestants = [
+            Contestant::new("random", Random, |_: &PreGame, seed| Box::new(RandomBot::new(seed))),
+            Contestant::new("hunter", Random,
+                |pregame: &PreGame, seed| Box::new(HuntTargetBot::new(pregame, seed))),
+            Contestant::new("probability", Random,
+                |pregame: &PreGame, seed| Box::new(ProbabilityBot::new(pregame, seed))),
+            Contestant::new("tunable", Random,
+                |pregame: &PreGame, seed| Box::new(TunableBot::new(pregame, Difficulty::easy(), seed))),
+            Contestant::new("adaptive", Random,
+                move |pregame: &PreGame, seed| Box::new(AdaptiveBot::new(pregame, &history, seed))),
+        ];
+
+        for contestant in &contestants {
+            let report = play_match(armored, contestant, contestant, 4, 1).unwrap();
+            assert_eq!(4, report.first().wins() + report.second().wins() + report.draws());
+        }
+    }
+
+    #[test]
+    fn should_play_simultaneous_turns() {
+        let simultaneous = || {
+            let mut pregame = setup();
+            pregame.set_simultaneous_turns(true);
+            pregame
+        };
+        let hunter = Contestant::new("hunter", Random,
+            |pregame: &PreGame, seed| Box::new(HuntTargetBot::new(pregame, seed)));
+        let random = Contestant::new("random", Random, |_: &PreGame, seed| Box::new(RandomBot::new(seed)));
+
+        let report = play_match(simultaneous, &hunter, &random, 10, 1).unwrap();
+        assert_eq!(10, report.first().wins() + report.second().wins() + report.draws());
+        assert!(report.first().wins() > report.second().wins());
+    }
+
+    #[test]
+    fn should_fail_on_broken_setups() {
+        let random = Contestant::new("random", Random, |_: &PreGame, seed| Box::new(RandomBot::new(seed)));
+        let cramped = || {
+            let mut pregame = PreGame::new(2, 2).unwrap();
+            pregame.add_ship_type("Corvette", 2).unwrap();
+            pregame.add_ship_type("Frigate", 2).unwrap();
+            pregame.add_ship_type("Submarine", 1).unwrap();
+            pregame
+        };
+
+        assert_eq!(Err(FleetDoesNotFit), play_match(cramped, &random, &random, 1, 1));
+        assert_eq!(Err(IllegalSetup), play_match(|| PreGame::new(2, 2).unwrap(), &random, &random, 1, 1));
+    }
+}